@@ -0,0 +1,209 @@
+use budget::Budget;
+use std::time::Duration;
+
+pub const DAYS: u32 = 25;
+
+pub fn input(day: u32) -> Option<&'static str> {
+    match day {
+        1 => Some(include_str!("../../day01/src/input.txt")),
+        2 => Some(include_str!("../../day02/src/input.txt")),
+        3 => Some(include_str!("../../day03/src/input.txt")),
+        4 => Some(include_str!("../../day04/src/input.txt")),
+        5 => Some(include_str!("../../day05/src/input.txt")),
+        6 => Some(include_str!("../../day06/src/input.txt")),
+        7 => Some(include_str!("../../day07/src/input.txt")),
+        8 => Some(include_str!("../../day08/src/input.txt")),
+        9 => Some(include_str!("../../day09/src/input.txt")),
+        10 => Some(include_str!("../../day10/src/input.txt")),
+        11 => Some(include_str!("../../day11/src/input.txt")),
+        12 => Some(include_str!("../../day12/src/input.txt")),
+        13 => Some(include_str!("../../day13/src/input.txt")),
+        14 => Some(include_str!("../../day14/src/input.txt")),
+        15 => Some("0,12,6,13,20,1,17"),
+        16 => Some(include_str!("../../day16/src/input.txt")),
+        17 => Some(include_str!("../../day17/src/input.txt")),
+        _ => None,
+    }
+}
+
+// bounds for the solvers that may never finish (days 7, 11 and 16), every part gets a fresh budget
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Limits {
+    pub max_steps: Option<u64>,
+    pub timeout: Option<Duration>,
+}
+
+impl Limits {
+    fn budget(&self) -> Budget {
+        let mut budget = Budget::unlimited();
+        if let Some(max_steps) = self.max_steps {
+            budget = budget.with_max_steps(max_steps);
+        }
+        if let Some(timeout) = self.timeout {
+            budget = budget.with_timeout(timeout);
+        }
+        budget
+    }
+}
+
+// Err if the solver can't find an answer for the input
+pub fn solve(
+    day: u32,
+    input: &str,
+    parallel: bool,
+    limits: Limits,
+) -> Result<(String, String), String> {
+    let solution = match day {
+        1 => {
+            let parsed = day01::parse(input);
+            both(day01::part1(&parsed), day01::part2(&parsed))
+        }
+        2 => {
+            let parsed = day02::parse(input);
+            let part2 =
+                day02::part2(&parsed).map_err(|(line, e)| format!("line {}: {}", line, e))?;
+            both(day02::part1(&parsed), part2)
+        }
+        3 => {
+            let parsed = day03::parse(input);
+            if parallel {
+                both(day03::part1(&parsed), day03::part2_parallel(&parsed))
+            } else {
+                both(day03::part1(&parsed), day03::part2(&parsed))
+            }
+        }
+        4 => {
+            let parsed = day04::parse(input);
+            both(day04::part1(&parsed), day04::part2(&parsed))
+        }
+        5 => {
+            let parsed = day05::parse(input);
+            both(day05::part1(&parsed), day05::part2(&parsed))
+        }
+        6 => {
+            let parsed = day06::parse(input);
+            both(day06::part1(&parsed), day06::part2(&parsed))
+        }
+        7 => {
+            let parsed = day07::parse(input);
+            let part2 = day07::part2_with_budget(&parsed, &mut limits.budget())
+                .map_err(|e| e.to_string())?;
+            both(day07::part1(&parsed), part2)
+        }
+        8 => {
+            let parsed = day08::parse(input);
+            if parallel {
+                both(day08::part1(&parsed), day08::part2_parallel(&parsed))
+            } else {
+                both(day08::part1(&parsed), day08::part2(parsed))
+            }
+        }
+        9 => {
+            let parsed = day09::parse(input);
+            let solution = day09::part1(&parsed);
+            both(solution, day09::part2(&parsed, solution))
+        }
+        10 => {
+            let mut parsed = day10::parse(input);
+            parsed.sort_unstable();
+            both(day10::part1(&parsed), day10::part2(&parsed))
+        }
+        11 => {
+            let parsed = day11::parse(input);
+            let part = |seat_iter| {
+                day11::part_with_budget(&parsed, seat_iter, &mut limits.budget())
+                    .map_err(|e| e.to_string())
+            };
+            both(
+                part(day11::next_state_part1)?,
+                part(day11::next_state_part2)?,
+            )
+        }
+        12 => {
+            let parsed = day12::parse(input);
+            both(day12::part1(&parsed), day12::part2(&parsed))
+        }
+        13 => {
+            let (time, busses) = day13::parse(input);
+            both(day13::part1(time, &busses), day13::part2(&busses))
+        }
+        14 => {
+            let parsed = day14::parse(input);
+            both(day14::part1(&parsed), day14::part2(&parsed))
+        }
+        15 => {
+            let parsed = day15::parse(input);
+            both(day15::part1(&parsed), day15::part2(&parsed))
+        }
+        16 => {
+            let parsed = day16::parse(input);
+            let part2 = day16::part2_with_budget(&parsed, &mut limits.budget())
+                .map_err(|e| e.to_string())?;
+            both(day16::part1(&parsed), part2)
+        }
+        17 => {
+            let parsed = day17::parse(input);
+            if parallel {
+                both(
+                    day17::part1_parallel(&parsed),
+                    day17::part2_parallel(&parsed),
+                )
+            } else {
+                both(day17::part1(&parsed), day17::part2(&parsed))
+            }
+        }
+        18 => {
+            let parsed = day18::parse(input);
+            both(day18::part1(&parsed), day18::part2(&parsed))
+        }
+        19 => {
+            let parsed = day19::parse(input);
+            both(day19::part1(&parsed), day19::part2(&parsed))
+        }
+        20 => {
+            let parsed = day20::parse(input);
+            both(day20::part1(&parsed), day20::part2(&parsed))
+        }
+        21 => {
+            let parsed = day21::parse(input);
+            let part2 = day21::part2(&parsed).map_err(|e| e.to_string())?;
+            both(day21::part1(&parsed), part2)
+        }
+        22 => {
+            let (player1, player2) = day22::parse(input);
+            both(
+                day22::part1(&player1, &player2),
+                day22::part2(&player1, &player2),
+            )
+        }
+        23 => {
+            let parsed = day23::parse(input);
+            both(day23::part1(&parsed), day23::part2(&parsed))
+        }
+        24 => {
+            let parsed = day24::parse(input);
+            both(day24::part1(&parsed), day24::part2(&parsed))
+        }
+        25 => {
+            // the last day only has a single puzzle
+            let (card_key, door_key) = day25::parse(input);
+            both(day25::part1(card_key, door_key), "-")
+        }
+        _ => return Err(format!("day {} has no solver", day)),
+    };
+    Ok(solution)
+}
+
+pub fn lint(day: u32, input: &str) -> Option<Vec<(usize, String)>> {
+    match day {
+        5 => Some(day05::lint(input)),
+        10 => Some(day10::lint(input)),
+        14 => Some(day14::lint(input)),
+        16 => Some(day16::lint(input)),
+        _ => None,
+    }
+}
+
+fn both<A: ToString, B: ToString>(part1: A, part2: B) -> (String, String) {
+    (part1.to_string(), part2.to_string())
+}
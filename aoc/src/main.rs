@@ -0,0 +1,151 @@
+mod days;
+
+use std::env;
+use std::fs;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+const USAGE: &str = "usage:
+    aoc run [OPTIONS] [DAY[:FILE]]...  solve the given days (every bundled input if none are given)
+    aoc lint DAY [FILE]                check an input against the assumptions of the solver
+
+run options:
+    --parallel       solve every day on its own thread
+    --max-steps N    give up on days 7, 11 and 16 after N steps of a part
+    --timeout SECS   give up on days 7, 11 and 16 after SECS seconds of a part";
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    let result = match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..]),
+        Some("lint") => lint(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(2);
+        }
+    }
+}
+
+fn parse_day(arg: &str) -> Result<u32, String> {
+    match arg.parse::<u32>() {
+        Ok(day) if (1..=days::DAYS).contains(&day) => Ok(day),
+        _ => Err(format!("invalid day {:?}, expected 1-{}", arg, days::DAYS)),
+    }
+}
+
+fn read_input(day: u32, file: Option<&String>) -> Result<(String, String), String> {
+    match file {
+        Some(path) => fs::read_to_string(path)
+            .map(|input| (path.clone(), input))
+            .map_err(|e| format!("could not read {}: {}", path, e)),
+        None => days::input(day)
+            .map(|input| (format!("day{:02}/src/input.txt", day), input.to_string()))
+            .ok_or_else(|| format!("day {} has no bundled input, pass a file", day)),
+    }
+}
+
+fn option_value<T: std::str::FromStr>(option: &str, value: Option<&String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("{} needs a value", option))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid value {:?} for {}", value, option))
+}
+
+fn run(mut args: &[String]) -> Result<bool, String> {
+    let mut parallel = false;
+    let mut limits = days::Limits::default();
+    while let Some(option) = args.first().filter(|arg| arg.starts_with("--")) {
+        let value = args.get(1);
+        args = match option.as_str() {
+            "--parallel" => {
+                parallel = true;
+                &args[1..]
+            }
+            "--max-steps" => {
+                limits.max_steps = Some(option_value(option, value)?);
+                &args[2..]
+            }
+            "--timeout" => {
+                let seconds = option_value::<f64>(option, value)?;
+                let timeout = Duration::try_from_secs_f64(seconds)
+                    .map_err(|_| format!("invalid value {:?} for {}", value.unwrap(), option))?;
+                limits.timeout = Some(timeout);
+                &args[2..]
+            }
+            _ => return Err(format!("unknown option {}\n{}", option, USAGE)),
+        };
+    }
+
+    let selected = if args.is_empty() {
+        (1..=days::DAYS)
+            .filter(|&day| days::input(day).is_some())
+            .map(|day| (day, None))
+            .collect()
+    } else {
+        args.iter()
+            .map(|arg| match arg.split_once(':') {
+                Some((day, file)) => Ok((parse_day(day)?, Some(file.to_string()))),
+                None => Ok((parse_day(arg)?, None)),
+            })
+            .collect::<Result<Vec<_>, String>>()?
+    };
+    let inputs = selected
+        .iter()
+        .map(|(day, file)| read_input(*day, file.as_ref()).map(|(_, input)| (*day, input)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // a day without an answer doesn't stop the others, but fails the run
+    let mut solved = true;
+    let mut report = |day: u32, solution: Result<(String, String), String>| match solution {
+        Ok((part1, part2)) => println!("day{:02}: {} {}", day, part1, part2),
+        Err(message) => {
+            eprintln!("day{:02}: {}", day, message);
+            solved = false;
+        }
+    };
+
+    if parallel {
+        // every day gets its own thread, results are still printed in order
+        thread::scope(|scope| {
+            let handles = inputs
+                .iter()
+                .map(|(day, input)| scope.spawn(move || days::solve(*day, input, true, limits)))
+                .collect::<Vec<_>>();
+
+            for ((day, _), handle) in inputs.iter().zip(handles) {
+                report(*day, handle.join().unwrap());
+            }
+        });
+    } else {
+        for (day, input) in &inputs {
+            report(*day, days::solve(*day, input, false, limits));
+        }
+    }
+
+    Ok(solved)
+}
+
+fn lint(args: &[String]) -> Result<bool, String> {
+    let day = match args {
+        [day] | [day, _] => parse_day(day)?,
+        _ => return Err(USAGE.to_string()),
+    };
+    let (name, input) = read_input(day, args.get(1))?;
+
+    let diagnostics =
+        days::lint(day, &input).ok_or_else(|| format!("day {} has no input validator", day))?;
+
+    for (line, message) in &diagnostics {
+        println!("{}:{}: {}", name, line, message);
+    }
+
+    Ok(diagnostics.is_empty())
+}
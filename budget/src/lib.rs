@@ -0,0 +1,120 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+use core::fmt;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exhausted {
+    Steps(u64),
+    Time(Duration),
+}
+
+impl fmt::Display for Exhausted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Exhausted::Steps(steps) => write!(f, "gave up after {} steps", steps),
+            Exhausted::Time(timeout) => write!(f, "gave up after {:?}", timeout),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Exhausted {}
+
+#[derive(Debug, Clone)]
+pub struct Budget {
+    steps: u64,
+    max_steps: Option<u64>,
+    #[cfg(feature = "std")]
+    started: Instant,
+    #[cfg(feature = "std")]
+    timeout: Option<Duration>,
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget::unlimited()
+    }
+}
+
+impl Budget {
+    pub fn unlimited() -> Self {
+        Budget {
+            steps: 0,
+            max_steps: None,
+            #[cfg(feature = "std")]
+            started: Instant::now(),
+            #[cfg(feature = "std")]
+            timeout: None,
+        }
+    }
+
+    pub fn steps(max_steps: u64) -> Self {
+        Budget::unlimited().with_max_steps(max_steps)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn timeout(timeout: Duration) -> Self {
+        Budget::unlimited().with_timeout(timeout)
+    }
+
+    pub fn with_max_steps(mut self, max_steps: u64) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    // the clock starts when the budget is created, not on the first step
+    #[cfg(feature = "std")]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn steps_taken(&self) -> u64 {
+        self.steps
+    }
+
+    pub fn step(&mut self) -> Result<(), Exhausted> {
+        if let Some(max_steps) = self.max_steps {
+            if self.steps >= max_steps {
+                return Err(Exhausted::Steps(max_steps));
+            }
+        }
+        #[cfg(feature = "std")]
+        if let Some(timeout) = self.timeout {
+            if self.started.elapsed() > timeout {
+                return Err(Exhausted::Time(timeout));
+            }
+        }
+        self.steps += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps() {
+        let mut budget = Budget::steps(3);
+        assert_eq!(Ok(()), budget.step());
+        assert_eq!(Ok(()), budget.step());
+        assert_eq!(Ok(()), budget.step());
+        assert_eq!(Err(Exhausted::Steps(3)), budget.step());
+        assert_eq!(3, budget.steps_taken());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_timeout() {
+        let mut budget = Budget::timeout(Duration::from_millis(0));
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(
+            Err(Exhausted::Time(Duration::from_millis(0))),
+            budget.step()
+        );
+    }
+}
@@ -0,0 +1,360 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+// handed out by insert, never reused after a remove
+pub type EntryId = usize;
+
+// the value combinations that currently add up to a tracked target, in ascending order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Matches {
+    pairs: BTreeSet<[i32; 2]>,
+    triples: BTreeSet<[i32; 3]>,
+    // every triple is listed under each of its values, so a remove only checks its own
+    triples_by_value: BTreeMap<i32, BTreeSet<[i32; 3]>>,
+}
+
+impl Matches {
+    fn build(target: i32, by_value: &BTreeMap<i32, BTreeSet<EntryId>>) -> Self {
+        let mut matches = Matches::default();
+        for &first in by_value.keys() {
+            if let Some(pair) = pair_with(target, first) {
+                matches.add_pair(pair, by_value);
+            }
+            for &second in by_value.range(first..).map(|(value, _)| value) {
+                if let Some(triple) = triple_with(target, first, second) {
+                    matches.add_triple(triple, by_value);
+                }
+            }
+        }
+        matches
+    }
+
+    fn inserted(&mut self, target: i32, value: i32, by_value: &BTreeMap<i32, BTreeSet<EntryId>>) {
+        if let Some(pair) = pair_with(target, value) {
+            self.add_pair(pair, by_value);
+        }
+        for &other in by_value.keys() {
+            if let Some(triple) = triple_with(target, value, other) {
+                self.add_triple(triple, by_value);
+            }
+        }
+    }
+
+    fn removed(&mut self, target: i32, value: i32, by_value: &BTreeMap<i32, BTreeSet<EntryId>>) {
+        if let Some(pair) = pair_with(target, value) {
+            if !available(&pair, by_value) {
+                self.pairs.remove(&pair);
+            }
+        }
+        let broken = self
+            .triples_by_value
+            .get(&value)
+            .into_iter()
+            .flatten()
+            .filter(|triple| !available(&triple[..], by_value))
+            .copied()
+            .collect::<Vec<_>>();
+        for triple in broken {
+            self.triples.remove(&triple);
+            for value in &triple {
+                if let Some(triples) = self.triples_by_value.get_mut(value) {
+                    triples.remove(&triple);
+                    if triples.is_empty() {
+                        self.triples_by_value.remove(value);
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_pair(&mut self, pair: [i32; 2], by_value: &BTreeMap<i32, BTreeSet<EntryId>>) {
+        if available(&pair, by_value) {
+            self.pairs.insert(pair);
+        }
+    }
+
+    fn add_triple(&mut self, triple: [i32; 3], by_value: &BTreeMap<i32, BTreeSet<EntryId>>) {
+        if available(&triple, by_value) && self.triples.insert(triple) {
+            for &value in &triple {
+                self.triples_by_value
+                    .entry(value)
+                    .or_default()
+                    .insert(triple);
+            }
+        }
+    }
+}
+
+fn pair_with(target: i32, value: i32) -> Option<[i32; 2]> {
+    let other = i32::try_from(target as i64 - value as i64).ok()?;
+    Some([value.min(other), value.max(other)])
+}
+
+fn triple_with(target: i32, first: i32, second: i32) -> Option<[i32; 3]> {
+    let third = i32::try_from(target as i64 - first as i64 - second as i64).ok()?;
+    let mut triple = [first, second, third];
+    triple.sort_unstable();
+    Some(triple)
+}
+
+// there are enough entries to use every value as often as it's listed
+fn available(values: &[i32], by_value: &BTreeMap<i32, BTreeSet<EntryId>>) -> bool {
+    values.iter().all(|value| {
+        let needed = values.iter().filter(|&v| v == value).count();
+        by_value.get(value).map_or(0, BTreeSet::len) >= needed
+    })
+}
+
+// Answers for tracked targets are kept up to date by insert and remove, so a query only looks up
+// the first match. Other targets are answered by scanning the values.
+#[derive(Debug, Clone)]
+pub struct ExpenseIndex {
+    entries: BTreeMap<EntryId, i32>,
+    by_value: BTreeMap<i32, BTreeSet<EntryId>>,
+    next_id: EntryId,
+    tracked: BTreeMap<i32, Matches>,
+}
+
+// tracks 2020 for part1 and part2
+impl Default for ExpenseIndex {
+    fn default() -> Self {
+        let mut index = ExpenseIndex {
+            entries: BTreeMap::new(),
+            by_value: BTreeMap::new(),
+            next_id: 0,
+            tracked: BTreeMap::new(),
+        };
+        index.track(2020);
+        index
+    }
+}
+
+impl ExpenseIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // keeps the pairs and triples adding up to target from now on
+    pub fn track(&mut self, target: i32) {
+        if !self.tracked.contains_key(&target) {
+            let matches = Matches::build(target, &self.by_value);
+            self.tracked.insert(target, matches);
+        }
+    }
+
+    pub fn is_tracked(&self, target: i32) -> bool {
+        self.tracked.contains_key(&target)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, id: EntryId) -> Option<i32> {
+        self.entries.get(&id).copied()
+    }
+
+    // O(V log V) for every tracked target, V being the number of distinct values
+    pub fn insert(&mut self, value: i32) -> EntryId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.entries.insert(id, value);
+        self.by_value.entry(value).or_default().insert(id);
+        for (&target, matches) in &mut self.tracked {
+            matches.inserted(target, value, &self.by_value);
+        }
+        id
+    }
+
+    pub fn remove(&mut self, id: EntryId) -> Option<i32> {
+        let value = self.entries.remove(&id)?;
+        let ids = self.by_value.get_mut(&value).unwrap();
+        ids.remove(&id);
+        if ids.is_empty() {
+            self.by_value.remove(&value);
+        }
+        for (&target, matches) in &mut self.tracked {
+            matches.removed(target, value, &self.by_value);
+        }
+        Some(value)
+    }
+
+    // two different entries adding up to target, ordered by id like find_k_sum
+    pub fn find_pair(&self, target: i32) -> Option<Vec<(EntryId, i32)>> {
+        let pair = match self.tracked.get(&target) {
+            Some(matches) => *matches.pairs.iter().next()?,
+            None => self.scan_pair(target)?,
+        };
+        Some(self.take(&pair))
+    }
+
+    // three different entries adding up to target, ordered by id like find_k_sum
+    pub fn find_triple(&self, target: i32) -> Option<Vec<(EntryId, i32)>> {
+        let triple = match self.tracked.get(&target) {
+            Some(matches) => *matches.triples.iter().next()?,
+            None => self.scan_triple(target)?,
+        };
+        Some(self.take(&triple))
+    }
+
+    pub fn part1(&self) -> Option<i32> {
+        Some(product(&self.find_pair(2020)?))
+    }
+
+    pub fn part2(&self) -> Option<i32> {
+        Some(product(&self.find_triple(2020)?))
+    }
+
+    // the same match a tracked target would give, the smallest values first
+    fn scan_pair(&self, target: i32) -> Option<[i32; 2]> {
+        self.by_value
+            .keys()
+            .filter_map(|&first| pair_with(target, first).filter(|pair| pair[0] == first))
+            .find(|pair| available(pair, &self.by_value))
+    }
+
+    fn scan_triple(&self, target: i32) -> Option<[i32; 3]> {
+        self.by_value.keys().find_map(|&first| {
+            self.by_value
+                .range(first..)
+                .filter_map(|(&second, _)| triple_with(target, first, second))
+                .find(|triple| triple[0] == first && available(triple, &self.by_value))
+        })
+    }
+
+    // one distinct entry for each of the available values, ordered by id
+    fn take(&self, values: &[i32]) -> Vec<(EntryId, i32)> {
+        let mut taken = values
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| {
+                let skip = values[..i].iter().filter(|&&v| v == value).count();
+                let &id = self.by_value[&value].iter().nth(skip).unwrap();
+                (id, value)
+            })
+            .collect::<Vec<_>>();
+        taken.sort_unstable();
+        taken
+    }
+}
+
+impl Extend<i32> for ExpenseIndex {
+    fn extend<T: IntoIterator<Item = i32>>(&mut self, values: T) {
+        for value in values {
+            self.insert(value);
+        }
+    }
+}
+
+fn product(entries: &[(EntryId, i32)]) -> i32 {
+    entries.iter().map(|&(_, value)| value).product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::find_k_sum;
+
+    #[test]
+    fn test_sample() {
+        let mut index = ExpenseIndex::new();
+        index.extend([1721, 979, 366, 299, 675, 1456].iter().copied());
+        assert_eq!(Some(514579), index.part1());
+        assert_eq!(Some(241861950), index.part2());
+        assert_eq!(Some(vec![(0, 1721), (3, 299)]), index.find_pair(2020));
+
+        assert_eq!(Some(1721), index.remove(0));
+        assert_eq!(None, index.remove(0));
+        assert_eq!(None, index.part1());
+
+        let first = index.insert(1010);
+        assert_eq!(None, index.find_pair(2020));
+        let second = index.insert(1010);
+        assert_eq!(
+            Some(vec![(first, 1010), (second, 1010)]),
+            index.find_pair(2020)
+        );
+    }
+
+    #[test]
+    fn test_matches_find_k_sum() {
+        let mut index = ExpenseIndex::new();
+        let mut ids = vec![];
+        // deterministic mix of inserts and removals with plenty of duplicates
+        let mut seed = 7u32;
+        for _ in 0..300 {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            let value = ((seed >> 16) % 40) as i32 - 10;
+            if (seed >> 24).is_multiple_of(3) && !ids.is_empty() {
+                let id = ids.remove((seed >> 8) as usize % ids.len());
+                index.remove(id);
+            } else {
+                ids.push(index.insert(value));
+            }
+
+            let values = ids
+                .iter()
+                .map(|&id| index.get(id).unwrap())
+                .collect::<Vec<_>>();
+            for target in [-15, 0, 20, 45] {
+                let pair = index.find_pair(target);
+                assert_eq!(find_k_sum(&values, 2, target).is_some(), pair.is_some());
+                if let Some(pair) = pair {
+                    assert_eq!(target, pair.iter().map(|&(_, v)| v).sum::<i32>());
+                }
+                let triple = index.find_triple(target);
+                assert_eq!(find_k_sum(&values, 3, target).is_some(), triple.is_some());
+                if let Some(triple) = triple {
+                    assert_eq!(target, triple.iter().map(|&(_, v)| v).sum::<i32>());
+                    assert!(triple.windows(2).all(|w| w[0].0 < w[1].0));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_incremental() {
+        let targets = [-15, 0, 20, 45];
+        let mut index = ExpenseIndex::new();
+        index.track(20);
+        let mut ids = vec![];
+        let mut seed = 11u32;
+        for step in 0..300 {
+            // a target tracked halfway through starts from the entries already in the index
+            if step == 150 {
+                for &target in &targets {
+                    index.track(target);
+                }
+            }
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            let value = ((seed >> 16) % 40) as i32 - 10;
+            if (seed >> 24).is_multiple_of(3) && !ids.is_empty() {
+                let id = ids.remove((seed >> 8) as usize % ids.len());
+                index.remove(id);
+            } else {
+                ids.push(index.insert(value));
+            }
+
+            // what insert and remove kept up to date is what a rebuild from scratch finds
+            for (&target, matches) in &index.tracked {
+                assert_eq!(&Matches::build(target, &index.by_value), matches);
+                assert_eq!(
+                    index.scan_pair(target),
+                    matches.pairs.iter().next().copied()
+                );
+                assert_eq!(
+                    index.scan_triple(target),
+                    matches.triples.iter().next().copied()
+                );
+            }
+        }
+        assert!(targets.iter().all(|&target| index.is_tracked(target)));
+        assert!(index.is_tracked(2020));
+        assert!(!index.is_tracked(2021));
+    }
+}
@@ -1,3 +1,14 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod expense_index;
+pub mod subset_sum;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
 pub fn parse(input: &str) -> Vec<i32> {
     input
         .lines()
@@ -6,32 +17,98 @@ pub fn parse(input: &str) -> Vec<i32> {
 }
 
 pub fn part1(input: &[i32]) -> i32 {
-    for first in input {
-        for second in input {
-            if first + second == 2020 {
-                return first * second;
-            }
-        }
-    }
-    unreachable!()
+    product(input, 2)
 }
 
 pub fn part2(input: &[i32]) -> i32 {
-    for first in input {
-        for second in input {
-            for third in input {
-                if first + second + third == 2020 {
-                    return first * second * third;
+    product(input, 3)
+}
+
+fn product(input: &[i32], k: usize) -> i32 {
+    find_k_sum(input, k, 2020)
+        .expect("no entries sum to 2020")
+        .iter()
+        .map(|&(_, value)| value)
+        .product()
+}
+
+// finds k entries at distinct indices that add up to target, ordered by index
+pub fn find_k_sum(entries: &[i32], k: usize, target: i32) -> Option<Vec<(usize, i32)>> {
+    let mut sorted = entries.iter().copied().enumerate().collect::<Vec<_>>();
+    sorted.sort_unstable_by_key(|&(_, value)| value);
+
+    let mut found = k_sum(&sorted, k, target as i64)?;
+    found.sort_unstable();
+    Some(found)
+}
+
+// sorted by value, sums are widened to i64 so they can't overflow
+fn k_sum(sorted: &[(usize, i32)], k: usize, target: i64) -> Option<Vec<(usize, i32)>> {
+    match k {
+        0 if target == 0 => Some(Vec::new()),
+        0 => None,
+        1 => sorted
+            .binary_search_by_key(&target, |&(_, value)| value as i64)
+            .ok()
+            .map(|i| vec![sorted[i]]),
+        2 => {
+            if sorted.is_empty() {
+                return None;
+            }
+            let (mut low, mut high) = (0, sorted.len() - 1);
+            while low < high {
+                let sum = sorted[low].1 as i64 + sorted[high].1 as i64;
+                match sum.cmp(&target) {
+                    Ordering::Less => low += 1,
+                    Ordering::Greater => high -= 1,
+                    Ordering::Equal => return Some(vec![sorted[low], sorted[high]]),
                 }
             }
+            None
         }
+        _ => sorted.iter().enumerate().find_map(|(i, &entry)| {
+            // an equal value before this one was already tried with a superset of the rest
+            if i > 0 && sorted[i - 1].1 == entry.1 {
+                return None;
+            }
+            let mut rest = k_sum(&sorted[i + 1..], k - 1, target - entry.1 as i64)?;
+            rest.push(entry);
+            Some(rest)
+        }),
     }
-    unreachable!()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn test_sample() {
+        let parsed = [1721, 979, 366, 299, 675, 1456];
+        assert_eq!(514579, part1(&parsed));
+        assert_eq!(241861950, part2(&parsed));
+        assert_eq!(
+            Some(vec![(0, 1721), (3, 299)]),
+            find_k_sum(&parsed, 2, 2020)
+        );
+        assert_eq!(None, find_k_sum(&parsed, 2, 1));
+    }
+
+    #[test]
+    fn test_distinct_indices() {
+        assert_eq!(None, find_k_sum(&[1010, 5], 2, 2020));
+        assert_eq!(
+            Some(vec![(1, 1010), (2, 1010)]),
+            find_k_sum(&[5, 1010, 1010], 2, 2020)
+        );
+        assert_eq!(None, find_k_sum(&[1, 2], 3, 3));
+        assert_eq!(Some(vec![]), find_k_sum(&[1, 2], 0, 0));
+        assert_eq!(
+            Some(vec![(0, 1), (1, 1), (2, 1), (3, 1)]),
+            find_k_sum(&[1, 1, 1, 1], 4, 4)
+        );
+    }
+
     #[test]
     fn run01() {
         let input = include_str!("./input.txt");
@@ -0,0 +1,183 @@
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+// for every (size, sum) pair, how many subsets of the entries from some index on reach it
+type Ways = BTreeMap<(usize, i64), u128>;
+
+// number of subsets (including the empty one) whose entries add up to target
+pub fn count(entries: &[i32], target: i32) -> u128 {
+    lookup(&ways(entries, None, target), 0, None, target as i64)
+}
+
+pub fn count_of_size(entries: &[i32], k: usize, target: i32) -> u128 {
+    lookup(&ways(entries, Some(k), target), 0, Some(k), target as i64)
+}
+
+// lazily yields the index sets of all subsets adding up to target
+pub fn subsets(entries: &[i32], target: i32) -> Subsets<'_> {
+    Subsets::new(entries, None, target)
+}
+
+pub fn subsets_of_size(entries: &[i32], k: usize, target: i32) -> Subsets<'_> {
+    Subsets::new(entries, Some(k), target)
+}
+
+pub struct Subsets<'a> {
+    entries: &'a [i32],
+    ways: Vec<Ways>,
+    // index to decide on next, entries still to pick, sum still to reach, picked indices
+    stack: Vec<(usize, Option<usize>, i64, Vec<usize>)>,
+}
+
+impl<'a> Subsets<'a> {
+    fn new(entries: &'a [i32], size: Option<usize>, target: i32) -> Self {
+        let ways = ways(entries, size, target);
+        let target = target as i64;
+        let stack = if lookup(&ways, 0, size, target) > 0 {
+            vec![(0, size, target, Vec::new())]
+        } else {
+            Vec::new()
+        };
+        Subsets {
+            entries,
+            ways,
+            stack,
+        }
+    }
+}
+
+impl Iterator for Subsets<'_> {
+    type Item = Vec<usize>;
+
+    // every frame on the stack can still be completed, so this never searches in vain
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((i, remaining, sum, picked)) = self.stack.pop() {
+            if i == self.entries.len() {
+                return Some(picked);
+            }
+
+            if lookup(&self.ways, i + 1, remaining, sum) > 0 {
+                self.stack.push((i + 1, remaining, sum, picked.clone()));
+            }
+
+            let entry = self.entries[i] as i64;
+            let taken = match remaining {
+                Some(0) => continue,
+                Some(k) => Some(k - 1),
+                None => None,
+            };
+            if lookup(&self.ways, i + 1, taken, sum - entry) > 0 {
+                let mut picked = picked;
+                picked.push(i);
+                self.stack.push((i + 1, taken, sum - entry, picked));
+            }
+        }
+        None
+    }
+}
+
+fn lookup(ways: &[Ways], i: usize, size: Option<usize>, sum: i64) -> u128 {
+    ways[i].get(&(size.unwrap_or(0), sum)).copied().unwrap_or(0)
+}
+
+// ways[i] only keeps sums that the entries before i can still top up to target,
+// which keeps the table at about entries * target for non-negative expenses.
+// The size is always 0 if it isn't constrained.
+fn ways(entries: &[i32], size: Option<usize>, target: i32) -> Vec<Ways> {
+    let target = target as i64;
+    let mut prefix = vec![(0i64, 0i64); entries.len() + 1];
+    for (i, &entry) in entries.iter().enumerate() {
+        let (negative, positive) = prefix[i];
+        prefix[i + 1] = (
+            negative + (entry as i64).min(0),
+            positive + (entry as i64).max(0),
+        );
+    }
+
+    let mut ways = vec![Ways::new(); entries.len() + 1];
+    ways[entries.len()].insert((0, 0), 1);
+
+    for i in (0..entries.len()).rev() {
+        let (negative, positive) = prefix[i];
+        let window = (target - positive)..=(target - negative);
+        let entry = entries[i] as i64;
+
+        let mut current = Ways::new();
+        for (&(picked, sum), &count) in &ways[i + 1] {
+            let taken = (picked + size.map_or(0, |_| 1), sum + entry);
+            for key in [(picked, sum), taken] {
+                if size.is_some_and(|k| key.0 > k) || !window.contains(&key.1) {
+                    continue;
+                }
+                // saturates instead of overflowing on huge inputs
+                let total = current.entry(key).or_insert(0);
+                *total = total.saturating_add(count);
+            }
+        }
+        ways[i] = current;
+    }
+
+    ways
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(entries: &[i32], size: Option<usize>, target: i32) -> Vec<Vec<usize>> {
+        (0u32..1 << entries.len())
+            .map(|mask| {
+                (0..entries.len())
+                    .filter(|i| mask & (1 << i) != 0)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|subset| size.is_none_or(|k| subset.len() == k))
+            .filter(|subset| subset.iter().map(|&i| entries[i]).sum::<i32>() == target)
+            .collect()
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let inputs: [&[i32]; 4] = [
+            &[1721, 979, 366, 299, 675, 1456],
+            &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+            &[3, 3, 3, 1, 1, 0, 2, 2],
+            &[-4, 7, -1, 3, 0, 5, -2, 6, 1],
+        ];
+        for entries in inputs.iter() {
+            for target in -8..=25 {
+                for size in [None, Some(0), Some(1), Some(2), Some(3), Some(5)] {
+                    let mut expected = brute_force(entries, size, target);
+                    expected.sort();
+                    let mut found = match size {
+                        Some(k) => subsets_of_size(entries, k, target).collect::<Vec<_>>(),
+                        None => subsets(entries, target).collect::<Vec<_>>(),
+                    };
+                    found.sort();
+                    assert_eq!(expected, found, "{:?} {:?} {}", entries, size, target);
+
+                    let counted = match size {
+                        Some(k) => count_of_size(entries, k, target),
+                        None => count(entries, target),
+                    };
+                    assert_eq!(expected.len() as u128, counted);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sample() {
+        let entries = [1721, 979, 366, 299, 675, 1456];
+        assert_eq!(
+            vec![vec![0, 3]],
+            subsets_of_size(&entries, 2, 2020).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![vec![1, 2, 4]],
+            subsets_of_size(&entries, 3, 2020).collect::<Vec<_>>()
+        );
+        assert_eq!(2, count(&entries, 2020));
+    }
+}
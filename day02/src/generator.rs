@@ -0,0 +1,226 @@
+use crate::Rule;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+// SplitMix64, the same sequence on every platform so fixtures can be regenerated from their seed
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // in 0..n, the modulo bias is negligible for the small n used here
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    fn chance(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+}
+
+// A password that is valid or invalid under the sled rental and toboggan policies as asked,
+// None leaves the outcome to chance. Returns None if the rule can't have such a password,
+// e.g. "2-2 a" is never valid under the toboggan policy.
+pub fn password(
+    rng: &mut Rng,
+    rule: &Rule,
+    sled: Option<bool>,
+    toboggan: Option<bool>,
+) -> Option<String> {
+    let sled = outcomes(rng, sled);
+    let toboggan = outcomes(rng, toboggan);
+    sled.iter()
+        .flat_map(|&sled| toboggan.iter().map(move |&toboggan| (sled, toboggan)))
+        .find_map(|(sled, toboggan)| build(rng, rule, sled, toboggan))
+}
+
+fn outcomes(rng: &mut Rng, wanted: Option<bool>) -> Vec<bool> {
+    match wanted {
+        Some(wanted) => vec![wanted],
+        None => {
+            let first = rng.chance();
+            vec![first, !first]
+        }
+    }
+}
+
+fn build(rng: &mut Rng, rule: &Rule, sled: bool, toboggan: bool) -> Option<String> {
+    let Rule { low, high, letter } = *rule;
+
+    // the 1-based positions the toboggan policy looks at and whether they hold the letter
+    let pinned = if toboggan {
+        if low == 0 || low == high {
+            return None;
+        }
+        let first = rng.chance();
+        vec![(low, first), (high, !first)]
+    } else if low == 0 {
+        // position 0 makes the entry invalid whatever the password is
+        vec![]
+    } else if low == high {
+        vec![(low, rng.chance())]
+    } else {
+        let both = rng.chance();
+        vec![(low, both), (high, both)]
+    };
+    let pinned_count = pinned.iter().filter(|&&(_, holds)| holds).count();
+
+    let count = if sled {
+        let from = low.max(pinned_count);
+        if from > high {
+            return None;
+        }
+        from + rng.below(high - from + 1)
+    } else if pinned_count < low && rng.chance() {
+        pinned_count + rng.below(low - pinned_count)
+    } else {
+        high.saturating_add(1).max(pinned_count) + rng.below(3)
+    };
+    let extras = count - pinned_count;
+
+    let last_pinned = pinned.iter().map(|&(position, _)| position).max();
+    let length = last_pinned.unwrap_or(0).max(pinned.len() + extras).max(1) + rng.below(4);
+
+    let mut holds = vec![None; length];
+    for &(position, holds_letter) in &pinned {
+        holds[position - 1] = Some(holds_letter);
+    }
+    let mut free = (0..length)
+        .filter(|&i| holds[i].is_none())
+        .collect::<Vec<_>>();
+    shuffle(rng, &mut free);
+    for (n, &i) in free.iter().enumerate() {
+        holds[i] = Some(n < extras);
+    }
+
+    Some(
+        holds
+            .into_iter()
+            .map(|holds| {
+                if holds.unwrap() {
+                    letter
+                } else {
+                    other_letter(rng, letter)
+                }
+            })
+            .collect(),
+    )
+}
+
+fn other_letter(rng: &mut Rng, letter: char) -> char {
+    loop {
+        let c = (b'a' + rng.below(26) as u8) as char;
+        if c != letter {
+            return c;
+        }
+    }
+}
+
+fn shuffle<T>(rng: &mut Rng, items: &mut [T]) {
+    for i in (1..items.len()).rev() {
+        items.swap(i, rng.below(i + 1));
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Database {
+    pub text: String,
+    // what part1 and part2 have to count for the text
+    pub sled_valid: usize,
+    pub toboggan_valid: usize,
+}
+
+// `lines` random entries of which exactly round(ratio * lines) are valid under each policy
+pub fn database(seed: u64, lines: usize, sled_ratio: f64, toboggan_ratio: f64) -> Database {
+    let mut rng = Rng::new(seed);
+    let sled = flags(&mut rng, lines, sled_ratio);
+    let toboggan = flags(&mut rng, lines, toboggan_ratio);
+
+    let mut text = String::new();
+    for (&sled, &toboggan) in sled.iter().zip(&toboggan) {
+        let low = 1 + rng.below(9);
+        let rule = Rule {
+            low,
+            high: low + 1 + rng.below(9),
+            letter: (b'a' + rng.below(26) as u8) as char,
+        };
+        // any outcome is possible for rules with 0 < low < high
+        let password = password(&mut rng, &rule, Some(sled), Some(toboggan)).unwrap();
+        writeln!(text, "{}: {}", rule, password).unwrap();
+    }
+
+    Database {
+        text,
+        sled_valid: sled.iter().filter(|&&valid| valid).count(),
+        toboggan_valid: toboggan.iter().filter(|&&valid| valid).count(),
+    }
+}
+
+fn flags(rng: &mut Rng, lines: usize, ratio: f64) -> Vec<bool> {
+    let valid = (ratio.clamp(0.0, 1.0) * lines as f64 + 0.5) as usize;
+    let mut flags = (0..lines).map(|i| i < valid).collect::<Vec<_>>();
+    shuffle(rng, &mut flags);
+    flags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, part1, part2, PasswordEntry, Policy, SledRental, Toboggan};
+    use core::str::FromStr;
+
+    #[test]
+    fn test_password() {
+        let mut rng = Rng::new(1);
+        for rule in ["1-3 a", "2-9 c", "1-2 z", "3-4 x"] {
+            let rule = Rule::from_str(rule).unwrap();
+            for &sled in &[true, false] {
+                for &toboggan in &[true, false] {
+                    for _ in 0..50 {
+                        let password =
+                            password(&mut rng, &rule, Some(sled), Some(toboggan)).unwrap();
+                        let entry =
+                            PasswordEntry::from_str(&format!("{}: {}", rule, password)).unwrap();
+                        assert_eq!(sled, SledRental.is_valid(&entry), "{}", entry);
+                        assert_eq!(toboggan, Toboggan.is_valid(&entry), "{}", entry);
+                    }
+                }
+            }
+        }
+
+        let rule = Rule::from_str("2-2 a").unwrap();
+        assert_eq!(None, password(&mut rng, &rule, None, Some(true)));
+        assert!(password(&mut rng, &rule, Some(true), Some(false)).is_some());
+        let rule = Rule::from_str("4-3 a").unwrap();
+        assert_eq!(None, password(&mut rng, &rule, Some(true), None));
+        let rule = Rule::from_str("0-3 a").unwrap();
+        assert_eq!(None, password(&mut rng, &rule, None, Some(true)));
+        assert!(password(&mut rng, &rule, Some(false), None).is_some());
+    }
+
+    #[test]
+    fn test_database() {
+        let database = super::database(7, 1000, 0.25, 0.8);
+        assert_eq!((250, 800), (database.sled_valid, database.toboggan_valid));
+        let parsed = parse(&database.text);
+        assert_eq!(1000, parsed.len());
+        assert_eq!(250, part1(&parsed));
+        assert_eq!(Ok(800), part2(&parsed));
+
+        assert_eq!(database.text, super::database(7, 1000, 0.25, 0.8).text);
+        assert_ne!(database.text, super::database(8, 1000, 0.25, 0.8).text);
+    }
+}
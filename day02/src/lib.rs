@@ -1,48 +1,690 @@
-use regex::Regex;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
-type Password = (usize, usize, char, String);
+extern crate alloc;
 
-pub fn parse(input: &str) -> Vec<Password> {
-    let pattern = Regex::new(r"^(\d+)-(\d+) (\w): (\w+)$").unwrap();
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
 
-    input
-        .lines()
-        .map(|line| {
-            let captures = pattern.captures(line).unwrap();
-            (
-                captures[1].parse().unwrap(),
-                captures[2].parse().unwrap(),
-                captures[3].chars().next().unwrap(),
-                captures[4].to_string(),
-            )
+pub mod generator;
+
+// Positions and counts are in chars (Unicode scalar values), not grapheme clusters: a letter
+// with a combining accent is two chars. The policy letter has to be a single char.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordEntry {
+    pub low: usize,
+    pub high: usize,
+    pub letter: char,
+    password: String,
+    // decided once when the entry is made, so lookups can index ASCII passwords by byte
+    ascii: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionError {
+    // positions are 1-based
+    Zero,
+    OutOfRange { position: usize, length: usize },
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionError::Zero => write!(f, "position 0, positions start at 1"),
+            PositionError::OutOfRange { position, length } => {
+                write!(
+                    f,
+                    "position {} is past the end of {} chars",
+                    position, length
+                )
+            }
+        }
+    }
+}
+
+impl PasswordEntry {
+    pub fn new(low: usize, high: usize, letter: char, password: String) -> Self {
+        PasswordEntry {
+            low,
+            high,
+            letter,
+            ascii: password.is_ascii(),
+            password,
+        }
+    }
+
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+
+    pub fn char_count(&self) -> usize {
+        if self.ascii {
+            self.password.len()
+        } else {
+            self.password.chars().count()
+        }
+    }
+
+    // the char at a 1-based position, ASCII passwords are indexed by byte
+    pub fn char_at(&self, position: usize) -> Result<char, PositionError> {
+        let index = position.checked_sub(1).ok_or(PositionError::Zero)?;
+        let found = if self.ascii {
+            self.password.as_bytes().get(index).map(|&b| b as char)
+        } else {
+            self.password.chars().nth(index)
+        };
+        found.ok_or(PositionError::OutOfRange {
+            position,
+            length: self.char_count(),
         })
-        .collect()
+    }
 }
 
-pub fn part1(input: &[Password]) -> usize {
-    input.iter().filter(|&pw| is_valid(pw)).count()
+// the "low-high letter" part of an entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    pub low: usize,
+    pub high: usize,
+    pub letter: char,
 }
 
-fn is_valid(pw: &Password) -> bool {
-    let count = pw.3.chars().filter(|&c| c == pw.2).count();
+impl FromStr for Rule {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (range, letter) = s.split_once(' ').ok_or(())?;
+        let (low, high) = range.split_once('-').ok_or(())?;
+
+        let mut letters = letter.chars();
+        let letter = letters.next().ok_or(())?;
+        if letters.next().is_some() {
+            return Err(());
+        }
 
-    ((pw.0)..=(pw.1)).contains(&count)
+        Ok(Rule {
+            low: low.parse().map_err(|_| ())?,
+            high: high.parse().map_err(|_| ())?,
+            letter,
+        })
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{} {}", self.low, self.high, self.letter)
+    }
 }
 
-pub fn part2(input: &[Password]) -> usize {
-    input.iter().filter(|&pw| is_valid2(pw)).count()
+impl FromStr for PasswordEntry {
+    type Err = ();
+
+    // "low-high letter: password", the password is any run of non-whitespace chars
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rule, password) = s.split_once(": ").ok_or(())?;
+        let Rule { low, high, letter } = Rule::from_str(rule)?;
+        if password.is_empty() || password.contains(char::is_whitespace) {
+            return Err(());
+        }
+
+        Ok(PasswordEntry::new(low, high, letter, password.to_string()))
+    }
 }
 
-fn is_valid2(pw: &Password) -> bool {
-    let f = pw.3.chars().nth(pw.0 - 1).map_or(false, |c| c == pw.2);
-    let s = pw.3.chars().nth(pw.1 - 1).map_or(false, |c| c == pw.2);
+impl fmt::Display for PasswordEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{} {}: {}",
+            self.low, self.high, self.letter, self.password
+        )
+    }
+}
 
-    f && !s || !f && s
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnosis {
+    pub policy: String,
+    // what the policy looked at, e.g. a letter count
+    pub observed: String,
+    // None if the entry is valid
+    pub failure: Option<String>,
+}
+
+pub trait Policy {
+    fn name(&self) -> String;
+
+    fn is_valid(&self, entry: &PasswordEntry) -> bool;
+
+    fn diagnose(&self, entry: &PasswordEntry) -> Diagnosis;
+}
+
+// the letter has to occur between low and high times
+pub struct SledRental;
+
+impl SledRental {
+    fn count(entry: &PasswordEntry) -> usize {
+        // an ASCII byte never occurs inside a multi-byte char
+        if entry.letter.is_ascii() {
+            let letter = entry.letter as u8;
+            entry.password.bytes().filter(|&b| b == letter).count()
+        } else {
+            entry
+                .password
+                .chars()
+                .filter(|&c| c == entry.letter)
+                .count()
+        }
+    }
+}
+
+impl Policy for SledRental {
+    fn name(&self) -> String {
+        "sled rental".to_string()
+    }
+
+    fn is_valid(&self, entry: &PasswordEntry) -> bool {
+        (entry.low..=entry.high).contains(&Self::count(entry))
+    }
+
+    fn diagnose(&self, entry: &PasswordEntry) -> Diagnosis {
+        let count = Self::count(entry);
+        Diagnosis {
+            policy: self.name(),
+            observed: format!("{} x {}", count, entry.letter),
+            failure: if self.is_valid(entry) {
+                None
+            } else {
+                Some(format!(
+                    "{} occurs {} times, expected {} to {}",
+                    entry.letter, count, entry.low, entry.high
+                ))
+            },
+        }
+    }
+}
+
+// The letter has to be at exactly one of the 1-based positions low and high. Both positions
+// have to lie within the password: is_valid treats an entry where they don't as invalid, part2
+// rejects it up front through validate. The original solution read a missing position as not
+// holding the letter, so e.g. "1-20 a: abc" used to count as valid.
+pub struct Toboggan;
+
+impl Toboggan {
+    // the 1-based line of the first entry with a position outside of its password
+    pub fn validate(entries: &[PasswordEntry]) -> Result<(), (usize, PositionError)> {
+        entries
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, entry)| Self::matches(entry).map(|_| ()).map_err(|e| (i + 1, e)))
+    }
+
+    fn matches(entry: &PasswordEntry) -> Result<(bool, bool), PositionError> {
+        Ok((
+            entry.char_at(entry.low)? == entry.letter,
+            entry.char_at(entry.high)? == entry.letter,
+        ))
+    }
+}
+
+impl Policy for Toboggan {
+    fn name(&self) -> String {
+        "toboggan".to_string()
+    }
+
+    fn is_valid(&self, entry: &PasswordEntry) -> bool {
+        matches!(Self::matches(entry), Ok((f, s)) if f != s)
+    }
+
+    fn diagnose(&self, entry: &PasswordEntry) -> Diagnosis {
+        let show = |position| match entry.char_at(position) {
+            Ok(c) => format!("{}@{}", c, position),
+            Err(_) => format!("-@{}", position),
+        };
+        Diagnosis {
+            policy: self.name(),
+            observed: format!("{} {}", show(entry.low), show(entry.high)),
+            failure: match Self::matches(entry) {
+                Err(e) => Some(e.to_string()),
+                Ok((true, true)) => Some(format!(
+                    "{} is at both {} and {}",
+                    entry.letter, entry.low, entry.high
+                )),
+                Ok((false, false)) => Some(format!(
+                    "{} is at neither {} nor {}",
+                    entry.letter, entry.low, entry.high
+                )),
+                Ok(_) => None,
+            },
+        }
+    }
+}
+
+pub struct MinDistinct(pub usize);
+
+impl MinDistinct {
+    fn distinct(entry: &PasswordEntry) -> usize {
+        let mut chars = entry.password.chars().collect::<Vec<_>>();
+        chars.sort_unstable();
+        chars.dedup();
+        chars.len()
+    }
+}
+
+impl Policy for MinDistinct {
+    fn name(&self) -> String {
+        format!("at least {} distinct", self.0)
+    }
+
+    fn is_valid(&self, entry: &PasswordEntry) -> bool {
+        Self::distinct(entry) >= self.0
+    }
+
+    fn diagnose(&self, entry: &PasswordEntry) -> Diagnosis {
+        let distinct = Self::distinct(entry);
+        Diagnosis {
+            policy: self.name(),
+            observed: format!("{} distinct", distinct),
+            failure: if distinct >= self.0 {
+                None
+            } else {
+                Some(format!("only {} distinct characters", distinct))
+            },
+        }
+    }
+}
+
+pub struct Forbidden(pub Vec<String>);
+
+impl Forbidden {
+    fn found<'a>(&'a self, entry: &PasswordEntry) -> Vec<&'a str> {
+        self.0
+            .iter()
+            .map(String::as_str)
+            .filter(|&s| entry.password.contains(s))
+            .collect()
+    }
+}
+
+impl Policy for Forbidden {
+    fn name(&self) -> String {
+        format!("none of {}", self.0.join(","))
+    }
+
+    fn is_valid(&self, entry: &PasswordEntry) -> bool {
+        !self.0.iter().any(|s| entry.password.contains(s.as_str()))
+    }
+
+    fn diagnose(&self, entry: &PasswordEntry) -> Diagnosis {
+        let found = self.found(entry);
+        Diagnosis {
+            policy: self.name(),
+            observed: if found.is_empty() {
+                "-".to_string()
+            } else {
+                found.join(",")
+            },
+            failure: if found.is_empty() {
+                None
+            } else {
+                Some(format!("contains {}", found.join(", ")))
+            },
+        }
+    }
+}
+
+pub struct All(pub Vec<Box<dyn Policy>>);
+
+impl Policy for All {
+    fn name(&self) -> String {
+        composite_name("all", &self.0)
+    }
+
+    fn is_valid(&self, entry: &PasswordEntry) -> bool {
+        self.0.iter().all(|policy| policy.is_valid(entry))
+    }
+
+    // fails with the reasons of every failing member
+    fn diagnose(&self, entry: &PasswordEntry) -> Diagnosis {
+        let (observed, failures) = diagnose_members(&self.0, entry);
+        Diagnosis {
+            policy: self.name(),
+            observed,
+            failure: if failures.is_empty() {
+                None
+            } else {
+                Some(failures.join("; "))
+            },
+        }
+    }
+}
+
+pub struct Any(pub Vec<Box<dyn Policy>>);
+
+impl Policy for Any {
+    fn name(&self) -> String {
+        composite_name("any", &self.0)
+    }
+
+    fn is_valid(&self, entry: &PasswordEntry) -> bool {
+        self.0.iter().any(|policy| policy.is_valid(entry))
+    }
+
+    // only fails if every member does, with all of their reasons
+    fn diagnose(&self, entry: &PasswordEntry) -> Diagnosis {
+        let (observed, failures) = diagnose_members(&self.0, entry);
+        Diagnosis {
+            policy: self.name(),
+            observed,
+            failure: if failures.len() < self.0.len() {
+                None
+            } else {
+                Some(failures.join("; "))
+            },
+        }
+    }
+}
+
+fn composite_name(kind: &str, members: &[Box<dyn Policy>]) -> String {
+    let names = members.iter().map(|p| p.name()).collect::<Vec<_>>();
+    format!("{}({})", kind, names.join(", "))
+}
+
+fn diagnose_members(members: &[Box<dyn Policy>], entry: &PasswordEntry) -> (String, Vec<String>) {
+    let diagnoses = members
+        .iter()
+        .map(|policy| policy.diagnose(entry))
+        .collect::<Vec<_>>();
+    let observed = diagnoses
+        .iter()
+        .map(|d| d.observed.as_str())
+        .collect::<Vec<_>>()
+        .join("; ");
+    let failures = diagnoses
+        .into_iter()
+        .filter_map(|d| Some(format!("{}: {}", d.policy, d.failure?)))
+        .collect();
+    (observed, failures)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportRow {
+    // 1-based line in the database
+    pub line: usize,
+    pub entry: String,
+    pub diagnosis: Diagnosis,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub rows: Vec<ReportRow>,
+}
+
+impl Report {
+    pub fn valid(&self) -> usize {
+        self.rows
+            .iter()
+            .filter(|row| row.diagnosis.failure.is_none())
+            .count()
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &ReportRow> {
+        self.rows
+            .iter()
+            .filter(|row| row.diagnosis.failure.is_some())
+    }
+}
+
+// a markdown table with one row per entry
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let header = ["line", "entry", "policy", "observed", "result"];
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                [
+                    row.line.to_string(),
+                    row.entry.clone(),
+                    row.diagnosis.policy.clone(),
+                    row.diagnosis.observed.clone(),
+                    match &row.diagnosis.failure {
+                        Some(failure) => format!("invalid: {}", failure),
+                        None => "valid".to_string(),
+                    },
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let mut widths = header.map(str::len);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let write_row = |f: &mut fmt::Formatter<'_>, cells: &[&str]| {
+            for (cell, width) in cells.iter().zip(&widths) {
+                write!(f, "| {:width$} ", cell, width = width)?;
+            }
+            writeln!(f, "|")
+        };
+        write_row(f, &header)?;
+        let rules = widths.map(|width| "-".repeat(width));
+        write_row(f, &rules.each_ref().map(String::as_str))?;
+        for row in &rows {
+            write_row(f, &row.each_ref().map(String::as_str))?;
+        }
+        Ok(())
+    }
+}
+
+pub fn report(input: &[PasswordEntry], policy: &dyn Policy) -> Report {
+    Report {
+        rows: input
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| ReportRow {
+                line: i + 1,
+                entry: entry.to_string(),
+                diagnosis: policy.diagnose(entry),
+            })
+            .collect(),
+    }
+}
+
+pub fn parse(input: &str) -> Vec<PasswordEntry> {
+    input
+        .lines()
+        .map(|line| PasswordEntry::from_str(line).unwrap())
+        .collect()
+}
+
+pub fn count_valid(input: &[PasswordEntry], policy: &dyn Policy) -> usize {
+    input.iter().filter(|entry| policy.is_valid(entry)).count()
+}
+
+pub fn part1(input: &[PasswordEntry]) -> usize {
+    count_valid(input, &SledRental)
+}
+
+pub fn part2(input: &[PasswordEntry]) -> Result<usize, (usize, PositionError)> {
+    Toboggan::validate(input)?;
+    Ok(count_valid(input, &Toboggan))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    const SAMPLE: &str = "1-3 a: abcde
+1-3 b: cdefg
+2-9 c: ccccccccc";
+
+    #[test]
+    fn test_sample() {
+        let parsed = parse(SAMPLE);
+        assert_eq!(2, part1(&parsed));
+        assert_eq!(Ok(1), part2(&parsed));
+    }
+
+    #[test]
+    fn test_policies() {
+        let parsed = parse(SAMPLE);
+        assert_eq!(2, count_valid(&parsed, &MinDistinct(5)));
+        assert_eq!(2, count_valid(&parsed, &Forbidden(vec!["cc".to_string()])));
+
+        let strict: Vec<Box<dyn Policy>> = vec![Box::new(SledRental), Box::new(Toboggan)];
+        assert_eq!(1, count_valid(&parsed, &All(strict)));
+        let lenient: Vec<Box<dyn Policy>> = vec![Box::new(SledRental), Box::new(Toboggan)];
+        assert_eq!(2, count_valid(&parsed, &Any(lenient)));
+
+        let nested = All(vec![
+            Box::new(Any(vec![Box::new(SledRental), Box::new(MinDistinct(5))])),
+            Box::new(Forbidden(vec!["abc".to_string()])),
+        ]);
+        assert_eq!(2, count_valid(&parsed, &nested));
+    }
+
+    #[test]
+    fn test_report() {
+        let parsed = parse(SAMPLE);
+        let report = report(&parsed, &Toboggan);
+        assert_eq!(1, report.valid());
+        assert_eq!(
+            Some("c is at both 2 and 9".to_string()),
+            report.rows[2].diagnosis.failure
+        );
+        assert_eq!(
+            "\
+| line | entry            | policy   | observed | result                           |
+| ---- | ---------------- | -------- | -------- | -------------------------------- |
+| 1    | 1-3 a: abcde     | toboggan | a@1 c@3  | valid                            |
+| 2    | 1-3 b: cdefg     | toboggan | c@1 e@3  | invalid: b is at neither 1 nor 3 |
+| 3    | 2-9 c: ccccccccc | toboggan | c@2 c@9  | invalid: c is at both 2 and 9    |
+",
+            report.to_string()
+        );
+
+        let both = All(vec![Box::new(SledRental), Box::new(MinDistinct(6))]);
+        let report = super::report(&parsed, &both);
+        assert_eq!(
+            Diagnosis {
+                policy: "all(sled rental, at least 6 distinct)".to_string(),
+                observed: "0 x b; 5 distinct".to_string(),
+                failure: Some(
+                    "sled rental: b occurs 0 times, expected 1 to 3; \
+                     at least 6 distinct: only 5 distinct characters"
+                        .to_string()
+                ),
+            },
+            report.rows[1].diagnosis
+        );
+        assert_eq!(3, report.failures().count());
+    }
+
+    #[test]
+    fn test_positions() {
+        let entry = PasswordEntry::from_str("0-4 a: abc").unwrap();
+        assert_eq!(Err(PositionError::Zero), entry.char_at(0));
+        assert_eq!(Ok('c'), entry.char_at(3));
+        assert_eq!(
+            Err(PositionError::OutOfRange {
+                position: 4,
+                length: 3
+            }),
+            entry.char_at(4)
+        );
+        assert!(!Toboggan.is_valid(&entry));
+        assert_eq!(
+            Some("position 0, positions start at 1".to_string()),
+            Toboggan.diagnose(&entry).failure
+        );
+
+        // part2 reports the first entry it can't check instead of counting it as invalid
+        let parsed = parse("1-3 a: abcde\n1-20 a: abc\n0-4 a: abc");
+        assert_eq!(1, count_valid(&parsed, &Toboggan));
+        assert_eq!(
+            Err((
+                2,
+                PositionError::OutOfRange {
+                    position: 20,
+                    length: 3
+                }
+            )),
+            part2(&parsed)
+        );
+        assert_eq!(
+            Err((2, PositionError::Zero)),
+            Toboggan::validate(&parse("1-3 a: abcde\n0-4 a: abc"))
+        );
+        assert_eq!(Ok(()), Toboggan::validate(&parse(SAMPLE)));
+
+        // positions count chars, so the combining accent of the second e is a position of its own
+        let entry = PasswordEntry::from_str("2-3 e: \u{e9}e\u{301}e-x").unwrap();
+        assert_eq!(6, entry.char_count());
+        assert_eq!(Ok('\u{301}'), entry.char_at(3));
+        assert!(Toboggan.is_valid(&entry));
+        assert!(SledRental.is_valid(&entry));
+        assert!(PasswordEntry::from_str("1-2 \u{e9}: \u{e9}\u{e9}").is_ok());
+
+        assert!(PasswordEntry::from_str("1-2 ab: abc").is_err());
+        assert!(PasswordEntry::from_str("1-2 a: a b").is_err());
+        assert!(PasswordEntry::from_str("1-2 a: ").is_err());
+        assert!(PasswordEntry::from_str("1 a: abc").is_err());
+    }
+
+    // cargo test --release -p day02 -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bench_million_lines() {
+        let database = generator::database(2020, 1_000_000, 0.4, 0.6).text;
+
+        let start = std::time::Instant::now();
+        let parsed = parse(&database);
+        let parsing = start.elapsed();
+        let first = part1(&parsed);
+        let sled = start.elapsed() - parsing;
+        let second = part2(&parsed).unwrap();
+        let toboggan = start.elapsed() - parsing - sled;
+        println!(
+            "parse {:?}, sled rental {:?} ({} valid), toboggan {:?} ({} valid)",
+            parsing, sled, first, toboggan, second
+        );
+
+        // the toboggan check as it was before positions were validated, walking the chars
+        let walk_chars = |entries: &[PasswordEntry]| {
+            entries
+                .iter()
+                .filter(|entry| {
+                    let at = |position: usize| entry.password.chars().nth(position - 1);
+                    (at(entry.low) == Some(entry.letter)) != (at(entry.high) == Some(entry.letter))
+                })
+                .count()
+        };
+        let compare = |name: &str, entries: &[PasswordEntry]| {
+            let start = std::time::Instant::now();
+            let old = walk_chars(entries);
+            let walking = start.elapsed();
+            let new = part2(entries).unwrap();
+            let indexing = start.elapsed() - walking;
+            assert_eq!(old, new);
+            println!(
+                "{}: walking chars {:?}, part2 {:?}",
+                name, walking, indexing
+            );
+        };
+        compare("million lines", &parsed);
+        let long = (0..1000)
+            .map(|i| {
+                let password = "x".repeat(100_000 + i) + "ab";
+                PasswordEntry::new(100_001 + i, 100_002 + i, 'a', password)
+            })
+            .collect::<Vec<_>>();
+        compare("1000 passwords of 100k chars", &long);
+    }
+
     #[test]
     fn run02() {
         let input = include_str!("./input.txt");
@@ -1,12 +1,206 @@
-pub fn parse(input: &str) -> Vec<&str> {
-    input.lines().collect()
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::BinaryHeap;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use core::fmt;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::thread;
+
+// (right, down)
+pub type Slope = (usize, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Survey {
+    pub trees: Vec<(Slope, u64)>,
+    pub fewest: u64,
+    pub safest: Vec<Slope>,
+    pub most: u64,
+    pub worst: Vec<Slope>,
+}
+
+// (right, down) steps a route may take, negative values go left or up
+pub type Move = (isize, isize);
+
+pub const FALLING: [Move; 3] = [(-1, 1), (0, 1), (1, 1)];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub trees: u64,
+    // (row, column) of every square on the route, columns are wrapped into the map width
+    pub path: Vec<(usize, usize)>,
+}
+
+// columns moved per row as an exact fraction, always reduced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ratio {
+    right: u64,
+    down: u64,
+}
+
+impl Ratio {
+    // None if down is 0
+    pub fn new(right: u64, down: u64) -> Option<Self> {
+        if down == 0 {
+            return None;
+        }
+        let divisor = gcd(right, down);
+        Some(Ratio {
+            right: right / divisor,
+            down: down / divisor,
+        })
+    }
+
+    pub fn right(&self) -> u64 {
+        self.right
+    }
+
+    pub fn down(&self) -> u64 {
+        self.down
+    }
+}
+
+impl FromStr for Ratio {
+    type Err = ();
+
+    // "3/2" or a whole number like "3"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (right, down) = s.split_once('/').unwrap_or((s, "1"));
+        Ratio::new(
+            right.trim().parse().map_err(|_| ())?,
+            down.trim().parse().map_err(|_| ())?,
+        )
+        .ok_or(())
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// every row packed into words of 64 squares, a set bit is a tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeMap {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    bits: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapError {
+    Empty,
+    // rows and columns are 0-based
+    Width {
+        row: usize,
+        width: usize,
+        expected: usize,
+    },
+    Square {
+        row: usize,
+        column: usize,
+        found: char,
+    },
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapError::Empty => write!(f, "the map is empty"),
+            MapError::Width {
+                row,
+                width,
+                expected,
+            } => write!(f, "row {} is {} wide, expected {}", row, width, expected),
+            MapError::Square { row, column, found } => {
+                write!(
+                    f,
+                    "unexpected {:?} at row {}, column {}",
+                    found, row, column
+                )
+            }
+        }
+    }
+}
+
+impl FromStr for TreeMap {
+    type Err = MapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let width = s.lines().next().map_or(0, |line| line.chars().count());
+        if width == 0 {
+            return Err(MapError::Empty);
+        }
+
+        let words_per_row = width.div_ceil(64);
+        let mut bits = Vec::new();
+        let mut height = 0;
+        for (row, line) in s.lines().enumerate() {
+            let mut words = vec![0u64; words_per_row];
+            let mut columns = 0;
+            for (column, c) in line.chars().enumerate() {
+                match c {
+                    '#' if column < width => words[column / 64] |= 1 << (column % 64),
+                    '.' | '#' => {}
+                    found => return Err(MapError::Square { row, column, found }),
+                }
+                columns += 1;
+            }
+            if columns != width {
+                return Err(MapError::Width {
+                    row,
+                    width: columns,
+                    expected: width,
+                });
+            }
+            bits.extend(words);
+            height += 1;
+        }
+
+        Ok(TreeMap {
+            width,
+            height,
+            words_per_row,
+            bits,
+        })
+    }
+}
+
+impl TreeMap {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // the map repeats to the right forever
+    pub fn is_tree(&self, row: usize, column: usize) -> bool {
+        let column = column % self.width;
+        let word = self.bits[row * self.words_per_row + column / 64];
+        word & (1 << (column % 64)) != 0
+    }
 }
 
-pub fn part1(input: &[&str]) -> u64 {
+pub fn parse(input: &str) -> TreeMap {
+    TreeMap::from_str(input).unwrap()
+}
+
+pub fn part1(input: &TreeMap) -> u64 {
     calculate(input, 3, 1)
 }
 
-pub fn part2(input: &[&str]) -> u64 {
+pub fn part2(input: &TreeMap) -> u64 {
     calculate(input, 1, 1)
         * calculate(input, 3, 1)
         * calculate(input, 5, 1)
@@ -14,26 +208,367 @@ pub fn part2(input: &[&str]) -> u64 {
         * calculate(input, 1, 2)
 }
 
-fn calculate(input: &[&str], right: usize, down: usize) -> u64 {
-    input
-        .iter()
-        .enumerate()
-        .filter(|(i, &_str)| i % down == 0)
+#[cfg(feature = "std")]
+pub fn part2_parallel(input: &TreeMap) -> u64 {
+    let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+    thread::scope(|scope| {
+        let handles = slopes
+            .iter()
+            .map(|&(right, down)| scope.spawn(move || calculate(input, right, down)))
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .product()
+    })
+}
+
+// tree counts for every slope with right in 0..=max_right and down in 1..=max_down, None if
+// there are none
+pub fn survey(input: &TreeMap, max_right: usize, max_down: usize) -> Option<Survey> {
+    let trees = (1..=max_down)
+        .flat_map(|down| (0..=max_right).map(move |right| (right, down)))
+        .map(|(right, down)| ((right, down), calculate(input, right, down)))
+        .collect::<Vec<_>>();
+
+    let fewest = trees.iter().map(|&(_, count)| count).min()?;
+    let most = trees.iter().map(|&(_, count)| count).max()?;
+    let with = |wanted| {
+        trees
+            .iter()
+            .filter(|&&(_, count)| count == wanted)
+            .map(|&(slope, _)| slope)
+            .collect()
+    };
+
+    Some(Survey {
+        safest: with(fewest),
+        worst: with(most),
+        trees,
+        fewest,
+        most,
+    })
+}
+
+// down has to be at least 1, the public entry points only pass valid slopes
+fn calculate(input: &TreeMap, right: usize, down: usize) -> u64 {
+    // only the offset within a map width matters, which also keeps the column from overflowing
+    let right = right % input.width;
+    (0..input.height)
+        .step_by(down)
         .enumerate()
-        .map(|(i, (_, &str))| {
-            if str.chars().nth((i * right) % str.len()).unwrap_or(' ') == '#' {
-                1
-            } else {
-                0
-            }
+        .filter(|&(i, row)| input.is_tree(row, i * right % input.width))
+        .count() as u64
+}
+
+// The map repeated as far to the right as the path gets, with the squares the path visits
+// marked O if they're open and X if they're a tree. Unlike the puzzle text the starting square is
+// marked as well, so there is an X for every tree calculate counts. None if down is 0.
+pub fn render(input: &TreeMap, right: usize, down: usize) -> Option<String> {
+    if down == 0 {
+        return None;
+    }
+    let steps = input.height.div_ceil(down);
+    let copies = (steps - 1) * right / input.width + 1;
+
+    let mut rendered = String::with_capacity(input.height * (input.width * copies + 1));
+    for row in 0..input.height {
+        if row > 0 {
+            rendered.push('\n');
+        }
+        let visited = if row % down == 0 {
+            Some(row / down * right)
+        } else {
+            None
+        };
+        for column in 0..input.width * copies {
+            rendered.push(
+                match (visited == Some(column), input.is_tree(row, column)) {
+                    (true, true) => 'X',
+                    (true, false) => 'O',
+                    (false, true) => '#',
+                    (false, false) => '.',
+                },
+            );
+        }
+    }
+    Some(rendered)
+}
+
+// Trees on every row where the line from the top-left square lands exactly on a square, which
+// is every down-th row. For a slope of right/down in lowest terms this is calculate(right, down).
+pub fn sample(input: &TreeMap, slope: Ratio) -> u64 {
+    let width = input.width as u128;
+    (0..input.height)
+        .step_by(slope.down as usize)
+        .filter(|&row| {
+            let column = row as u128 / slope.down as u128 * slope.right as u128;
+            input.is_tree(row, (column % width) as usize)
         })
-        .sum::<u64>()
+        .count() as u64
+}
+
+// Trees on every square the straight line from the centre of the top-left square to the bottom
+// row passes through. Touching a corner or an edge doesn't count.
+pub fn line_of_sight(input: &TreeMap, slope: Ratio) -> u64 {
+    let (right, down) = (slope.right as u128, slope.down as u128);
+    let width = input.width as u128;
+    let height = input.height as u128;
+
+    // With y and x in units of half a square the line is x = 1 + (right / down) * (y - 1).
+    // Scaling x by down keeps it whole, a column then spans 2 * down units.
+    let x = |y: u128| down + right * (y - 1);
+    let column_units = 2 * down;
+
+    let mut trees = 0;
+    for row in 0..height {
+        let (from, to) = (x((2 * row).max(1)), x((2 * row + 2).min(2 * height - 1)));
+        let first = from / column_units;
+        let last = if from == to {
+            first
+        } else {
+            to.div_ceil(column_units) - 1
+        };
+        trees += (first..=last)
+            .filter(|&column| input.is_tree(row as usize, (column % width) as usize))
+            .count() as u64;
+    }
+    trees
+}
+
+// The route from the top row to the bottom row that hits the fewest trees, starting in the given
+// column or anywhere on the top row. None if the moves can't reach the bottom.
+pub fn safest_route(input: &TreeMap, start: Option<usize>, moves: &[Move]) -> Option<Route> {
+    let (width, height) = (input.width, input.height);
+    let cost = |node: usize| input.is_tree(node / width, node % width) as u64;
+
+    let mut trees = vec![u64::MAX; width * height];
+    let mut previous = vec![usize::MAX; width * height];
+    let mut queue = BinaryHeap::new();
+    let starts = match start {
+        Some(column) => column % width..column % width + 1,
+        None => 0..width,
+    };
+    for node in starts {
+        trees[node] = cost(node);
+        queue.push(Reverse((trees[node], node)));
+    }
+
+    while let Some(Reverse((hit, node))) = queue.pop() {
+        if hit > trees[node] {
+            continue;
+        }
+        let (row, column) = (node / width, node % width);
+        if row == height - 1 {
+            let mut path = vec![(row, column)];
+            let mut current = node;
+            while previous[current] != usize::MAX {
+                current = previous[current];
+                path.push((current / width, current % width));
+            }
+            path.reverse();
+            return Some(Route { trees: hit, path });
+        }
+
+        for &(right, down) in moves {
+            let next_row = row as isize + down;
+            if next_row < 0 || next_row >= height as isize {
+                continue;
+            }
+            let next_column = (column as isize + right).rem_euclid(width as isize);
+            let next = next_row as usize * width + next_column as usize;
+            let next_hit = hit + cost(next);
+            if next_hit < trees[next] {
+                trees[next] = next_hit;
+                previous[next] = node;
+                queue.push(Reverse((next_hit, next)));
+            }
+        }
+    }
+
+    None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_survey() {
+        let input = include_str!("./sample.txt");
+        let parsed = parse(input);
+        assert_eq!(7, part1(&parsed));
+        assert_eq!(336, part2(&parsed));
+
+        let survey = survey(&parsed, 7, 2).unwrap();
+        assert_eq!(16, survey.trees.len());
+        for &(slope, trees) in &[
+            ((1, 1), 2),
+            ((3, 1), 7),
+            ((5, 1), 3),
+            ((7, 1), 4),
+            ((1, 2), 2),
+        ] {
+            assert!(survey.trees.contains(&(slope, trees)));
+        }
+        assert_eq!((0, vec![(5, 2)]), (survey.fewest, survey.safest));
+        assert_eq!((7, vec![(3, 1)]), (survey.most, survey.worst));
+
+        assert_eq!(None, super::survey(&parsed, 3, 0));
+    }
+
+    #[test]
+    fn test_tree_map() {
+        let wide = "#".repeat(130) + "\n" + &".".repeat(129) + "#";
+        let map = parse(&wide);
+        assert_eq!((130, 2), (map.width(), map.height()));
+        assert!(map.is_tree(0, 129) && map.is_tree(0, 130 * 7 + 64));
+        assert!(!map.is_tree(1, 128) && map.is_tree(1, 129) && map.is_tree(1, 259));
+
+        assert_eq!(Err(MapError::Empty), TreeMap::from_str(""));
+        assert_eq!(
+            Err(MapError::Width {
+                row: 1,
+                width: 2,
+                expected: 3
+            }),
+            TreeMap::from_str("#.#\n.#\n...")
+        );
+        assert_eq!(
+            Err(MapError::Square {
+                row: 0,
+                column: 1,
+                found: 'O'
+            }),
+            TreeMap::from_str(".O.")
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let parsed = parse(include_str!("./sample.txt"));
+        let rendered = render(&parsed, 3, 1).unwrap();
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert_eq!(11, lines.len());
+        assert_eq!("O.##.........##.........##.......", lines[0]);
+        assert_eq!("#..O#...#..#...#...#..#...#...#..", lines[1]);
+        assert_eq!(".#....X..#..#....#..#..#....#..#.", lines[2]);
+        assert_eq!(".#..#...#.#.#..#...#.#.#..#...X.#", lines[10]);
+        assert_eq!(7, rendered.matches('X').count());
+
+        for &(right, down) in &[(0, 1), (1, 2), (7, 1), (12, 3)] {
+            let rendered = render(&parsed, right, down).unwrap();
+            let trees = rendered.matches('X').count() as u64;
+            let open = rendered.matches('O').count() as u64;
+            assert_eq!(calculate(&parsed, right, down), trees);
+            assert_eq!(
+                (parsed.height() + down - 1) as u64 / down as u64,
+                trees + open
+            );
+        }
+
+        assert_eq!(
+            Some("O.\n#.\nO#".to_string()),
+            render(&parse("..\n#.\n.#"), 0, 2)
+        );
+        assert_eq!(None, render(&parsed, 1, 0));
+    }
+
+    fn check_route(map: &TreeMap, route: &Route, moves: &[Move]) {
+        assert_eq!(0, route.path[0].0);
+        assert_eq!(map.height() - 1, route.path.last().unwrap().0);
+        let width = map.width() as isize;
+        for pair in route.path.windows(2) {
+            let ((row, column), (next_row, next_column)) = (pair[0], pair[1]);
+            assert!(moves.iter().any(|&(right, down)| {
+                row as isize + down == next_row as isize
+                    && (column as isize + right).rem_euclid(width) == next_column as isize
+            }));
+        }
+        let trees = route
+            .path
+            .iter()
+            .filter(|&&(row, column)| map.is_tree(row, column))
+            .count();
+        assert_eq!(route.trees, trees as u64);
+    }
+
+    #[test]
+    fn test_safest_route() {
+        let map = parse(include_str!("./sample.txt"));
+
+        let straight = safest_route(&map, Some(0), &[(0, 1)]).unwrap();
+        assert_eq!(3, straight.trees);
+        assert_eq!(
+            (0..11).map(|row| (row, 0)).collect::<Vec<_>>(),
+            straight.path
+        );
+
+        // row by row minimum over the three falling moves
+        let mut best = (0..map.width())
+            .map(|column| map.is_tree(0, column) as u64)
+            .collect::<Vec<_>>();
+        for row in 1..map.height() {
+            best = (0..map.width())
+                .map(|column| {
+                    let above = (0..3)
+                        .map(|i| best[(column + map.width() + i - 1) % map.width()])
+                        .min()
+                        .unwrap();
+                    above + map.is_tree(row, column) as u64
+                })
+                .collect();
+        }
+        let route = safest_route(&map, None, &FALLING).unwrap();
+        assert_eq!(*best.iter().min().unwrap(), route.trees);
+        check_route(&map, &route, &FALLING);
+
+        let moves = [(1, 0), (0, 1), (-2, 2)];
+        let route = safest_route(&map, Some(4), &moves).unwrap();
+        assert_eq!((0, 4), route.path[0]);
+        check_route(&map, &route, &moves);
+        let straight = safest_route(&map, Some(4), &[(0, 1)]).unwrap();
+        assert!(route.trees <= straight.trees);
+
+        let walled = parse(".#.\n###\n...");
+        assert_eq!(1, safest_route(&walled, Some(0), &FALLING).unwrap().trees);
+        assert_eq!(None, safest_route(&walled, None, &[(1, 0)]));
+        assert_eq!(None, safest_route(&walled, None, &[(0, 3)]));
+    }
+
+    #[test]
+    fn test_rational_slopes() {
+        let map = parse(include_str!("./sample.txt"));
+        assert_eq!(Ratio::new(3, 2), Ratio::from_str("6/4").ok());
+        assert_eq!(Ratio::new(3, 1), Ratio::from_str("3").ok());
+        assert!(Ratio::from_str("1/0").is_err());
+
+        for &(right, down) in &[(3, 1), (1, 2), (3, 2), (0, 1), (5, 3)] {
+            let slope = Ratio::new(right as u64, down as u64).unwrap();
+            assert_eq!(calculate(&map, right, down), sample(&map, slope));
+        }
+
+        assert_eq!(3, line_of_sight(&map, Ratio::new(0, 1).unwrap()));
+        // through the corners only, so the same squares as the whole-step slope
+        assert_eq!(2, line_of_sight(&map, Ratio::new(1, 1).unwrap()));
+
+        // x goes from 0.5 to 2 over the four rows: (0, 0), (1, 0), (1, 1), (2, 1) and (3, 1)
+        let small = parse("#..\n##.\n.#.\n..#");
+        let half = Ratio::from_str("1/2").unwrap();
+        assert_eq!(4, line_of_sight(&small, half));
+        assert_eq!(2, sample(&small, half));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parallel() {
+        let input = include_str!("./input.txt");
+        let parsed = parse(input);
+        assert_eq!(part2(&parsed), part2_parallel(&parsed));
+    }
+
     #[test]
     fn run03() {
         let input = include_str!("./input.txt");
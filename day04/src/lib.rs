@@ -1,130 +1,352 @@
-use std::collections::HashMap;
-use std::str::FromStr;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-pub enum FieldType {
-    Byr, // (Birth Year)
-    Iyr, // (Issue Year)
-    Eyr, // (Expiration Year)
-    Hgt, // (Height)
-    Hcl, // (Hair Color)
-    Ecl, // (Eye Color)
-    Pid, // (Passport ID)
-    Cid, // (Country ID)
-}
+extern crate alloc;
 
-impl FromStr for FieldType {
-    type Err = ();
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "byr" => Ok(Self::Byr),
-            "iyr" => Ok(Self::Iyr),
-            "eyr" => Ok(Self::Eyr),
-            "hgt" => Ok(Self::Hgt),
-            "hcl" => Ok(Self::Hcl),
-            "ecl" => Ok(Self::Ecl),
-            "pid" => Ok(Self::Pid),
-            "cid" => Ok(Self::Cid),
-            _ => Err(()),
+pub type Passport = BTreeMap<String, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symbol {
+    Digit,
+    HexDigit,
+    Char(char),
+}
+
+impl Symbol {
+    fn matches(self, c: char) -> bool {
+        match self {
+            Symbol::Digit => c.is_ascii_digit(),
+            Symbol::HexDigit => c.is_ascii_hexdigit(),
+            Symbol::Char(expected) => c == expected,
         }
     }
 }
 
-impl FieldType {
-    fn is_valid(&self, value: &str) -> bool {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Constraint {
+    Any,
+    // inclusive
+    Range(i64, i64),
+    // a number followed by one of the units, each with its own inclusive range
+    Units(Vec<(String, i64, i64)>),
+    // one symbol per char, repeats are already expanded
+    Pattern(Vec<Symbol>),
+    OneOf(Vec<String>),
+}
+
+impl Constraint {
+    pub fn is_valid(&self, value: &str) -> bool {
+        let in_range = |number: &str, min: i64, max: i64| {
+            number
+                .parse::<i64>()
+                .is_ok_and(|number| (min..=max).contains(&number))
+        };
+
         match self {
-            Self::Byr => {
-                let parsed = value.parse::<i32>().unwrap_or(0);
-                (1920..=2002).contains(&parsed)
-            }
-            Self::Iyr => {
-                let parsed = value.parse::<i32>().unwrap_or(0);
-                (2010..=2020).contains(&parsed)
+            Constraint::Any => true,
+            Constraint::Range(min, max) => in_range(value, *min, *max),
+            Constraint::Units(units) => units.iter().any(|(unit, min, max)| {
+                value
+                    .strip_suffix(unit.as_str())
+                    .is_some_and(|number| in_range(number, *min, *max))
+            }),
+            Constraint::Pattern(symbols) => {
+                value.chars().count() == symbols.len()
+                    && value
+                        .chars()
+                        .zip(symbols)
+                        .all(|(c, symbol)| symbol.matches(c))
             }
-            Self::Eyr => {
-                let parsed = value.parse::<i32>().unwrap_or(0);
-                (2020..=2030).contains(&parsed)
+            Constraint::OneOf(values) => values.iter().any(|v| v == value),
+        }
+    }
+
+    fn parse(kind: &str, args: &[&str]) -> Result<Self, String> {
+        let number = |s: &str| {
+            s.parse::<i64>()
+                .map_err(|_| format!("{} is not a number", s))
+        };
+
+        match (kind, args) {
+            ("any", []) => Ok(Constraint::Any),
+            ("range", [min, max]) => Ok(Constraint::Range(number(min)?, number(max)?)),
+            ("units", _) if !args.is_empty() && args.len().is_multiple_of(3) => args
+                .chunks(3)
+                .map(|unit| Ok((unit[0].to_string(), number(unit[1])?, number(unit[2])?)))
+                .collect::<Result<_, _>>()
+                .map(Constraint::Units),
+            ("pattern", [pattern]) => parse_pattern(pattern).map(Constraint::Pattern),
+            ("one-of", _) if !args.is_empty() => Ok(Constraint::OneOf(
+                args.iter().map(|value| value.to_string()).collect(),
+            )),
+            _ => Err(format!("invalid constraint: {} {}", kind, args.join(" "))),
+        }
+    }
+}
+
+fn parse_pattern(pattern: &str) -> Result<Vec<Symbol>, String> {
+    let mut symbols = Vec::new();
+    let mut chars = pattern.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => symbols.push(match chars.next() {
+                Some('d') => Symbol::Digit,
+                Some('h') => Symbol::HexDigit,
+                Some(c) => Symbol::Char(c),
+                None => return Err("the pattern ends with \\".to_string()),
+            }),
+            '{' => {
+                let rest = chars.as_str();
+                let end = rest.find('}').ok_or("unclosed {")?;
+                let count = rest[..end]
+                    .parse::<usize>()
+                    .map_err(|_| format!("{} is not a repeat count", &rest[..end]))?;
+                let last = symbols.pop().ok_or("nothing to repeat")?;
+                symbols.extend(core::iter::repeat_n(last, count));
+                chars = rest[end + 1..].chars();
             }
-            Self::Hgt => {
-                let (height, unit) = value.split_at(value.len() - 2);
-                match unit {
-                    "cm" => {
-                        let parsed = height.parse::<i32>().unwrap_or(0);
-                        (150..=193).contains(&parsed)
-                    }
-                    "in" => {
-                        let parsed = height.parse::<i32>().unwrap_or(0);
-                        (59..=76).contains(&parsed)
-                    }
-                    _ => false,
-                }
+            c => symbols.push(Symbol::Char(c)),
+        }
+    }
+
+    Ok(symbols)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub required: bool,
+    pub constraint: Constraint,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schema {
+    pub fields: BTreeMap<String, Field>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    // 1-based
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl FromStr for Schema {
+    type Err = SchemaError;
+
+    // one "name required|optional constraint args..." per line, see schema.txt
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = BTreeMap::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
             }
-            Self::Hcl => {
-                value.len() == 7 && {
-                    let mut chars = value.chars();
-                    chars.next().unwrap() == '#' && chars.all(|c| c.is_ascii_hexdigit())
+            let error = |message: String| SchemaError {
+                line: i + 1,
+                message,
+            };
+
+            let words = line.split_ascii_whitespace().collect::<Vec<_>>();
+            let (name, required) = match words[..] {
+                [name, "required", ..] => (name, true),
+                [name, "optional", ..] => (name, false),
+                _ => {
+                    return Err(error(
+                        "expected a name and required or optional".to_string(),
+                    ))
                 }
+            };
+            let kind = words
+                .get(2)
+                .ok_or_else(|| error(format!("{} has no constraint", name)))?;
+            let constraint = Constraint::parse(kind, &words[3..]).map_err(error)?;
+
+            let field = Field {
+                required,
+                constraint,
+            };
+            if fields.insert(name.to_string(), field).is_some() {
+                return Err(error(format!("{} is declared twice", name)));
             }
-            Self::Ecl => matches!(value, "amb" | "blu" | "brn" | "gry" | "grn" | "hzl" | "oth"),
-            Self::Pid => value.len() == 9 && value.chars().all(|c| c.is_ascii_digit()),
-            Self::Cid => true,
         }
+
+        Ok(Schema { fields })
+    }
+}
+
+// the part 2 rules
+impl Default for Schema {
+    fn default() -> Self {
+        Schema::from_str(include_str!("./schema.txt")).unwrap()
+    }
+}
+
+impl Schema {
+    // every required field is present
+    pub fn is_complete(&self, passport: &Passport) -> bool {
+        self.fields
+            .iter()
+            .filter(|(_, field)| field.required)
+            .all(|(name, _)| passport.contains_key(name))
+    }
+
+    // complete, and every field is declared and satisfies its constraint
+    pub fn is_valid(&self, passport: &Passport) -> bool {
+        self.is_complete(passport)
+            && passport.iter().all(|(name, value)| {
+                self.fields
+                    .get(name)
+                    .is_some_and(|field| field.constraint.is_valid(value))
+            })
     }
 }
 
-pub fn parse(input: &str) -> Vec<HashMap<FieldType, String>> {
+pub fn parse(input: &str) -> Vec<Passport> {
     input
         .split("\n\n")
         .map(|line| {
             line.split_ascii_whitespace()
                 .map(|item| {
-                    let mut split = item.split(':');
-                    (
-                        split.next().unwrap().parse::<FieldType>().unwrap(),
-                        split.next().unwrap().to_string(),
-                    )
+                    let (key, value) = item.split_once(':').unwrap();
+                    (key.to_string(), value.to_string())
                 })
-                .collect::<HashMap<FieldType, String>>()
+                .collect::<Passport>()
         })
         .collect()
 }
 
-pub fn part1(input: &[HashMap<FieldType, String>]) -> usize {
+pub fn count_complete(input: &[Passport], schema: &Schema) -> usize {
     input
         .iter()
-        .map(|id| {
-            if id.contains_key(&FieldType::Cid) {
-                id.len() - 1
-            } else {
-                id.len()
-            }
-        })
-        .filter(|&valid_keys| valid_keys == 7)
+        .filter(|passport| schema.is_complete(passport))
         .count()
 }
 
-pub fn part2(input: &[HashMap<FieldType, String>]) -> usize {
+pub fn count_valid(input: &[Passport], schema: &Schema) -> usize {
     input
         .iter()
-        .filter(|&id| {
-            let all_valid = id
-                .iter()
-                .all(|(field_type, content)| field_type.is_valid(content));
-
-            if id.contains_key(&FieldType::Cid) {
-                (id.len() - 1) == 7 && all_valid
-            } else {
-                id.len() == 7 && all_valid
-            }
-        })
+        .filter(|passport| schema.is_valid(passport))
         .count()
 }
 
+pub fn part1(input: &[Passport]) -> usize {
+    count_complete(input, &Schema::default())
+}
+
+pub fn part2(input: &[Passport]) -> usize {
+    count_valid(input, &Schema::default())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sample() {
+        let parsed = parse(
+            "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in",
+        );
+        assert_eq!(2, part1(&parsed));
+
+        let invalid = parse(
+            "eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007",
+        );
+        assert_eq!(0, part2(&invalid));
+
+        let valid = parse(
+            "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719",
+        );
+        assert_eq!(4, part2(&valid));
+    }
+
+    #[test]
+    fn test_schema() {
+        let schema = Schema::default();
+        let check = |name: &str, value: &str| schema.fields[name].constraint.is_valid(value);
+        assert!(check("byr", "2002") && !check("byr", "2003"));
+        assert!(check("hgt", "60in") && check("hgt", "190cm"));
+        assert!(!check("hgt", "190in") && !check("hgt", "190") && !check("hgt", "cm"));
+        assert!(check("hcl", "#123abc") && !check("hcl", "#123abz") && !check("hcl", "123abc"));
+        assert!(check("ecl", "brn") && !check("ecl", "wat"));
+        assert!(check("pid", "000000001") && !check("pid", "0123456789"));
+
+        let custom = Schema::from_str(
+            "# a made up document
+id required pattern A\\d{2}-\\{x{3}
+size optional units kg 1 10 g 1000 10000",
+        )
+        .unwrap();
+        let passport = parse("id:A42-{xxx size:2000g")[0].clone();
+        assert!(custom.is_valid(&passport));
+        assert!(!custom.is_valid(&parse("id:A42-{xxx size:20kg")[0]));
+        assert!(!custom.is_valid(&parse("id:A42-{xxx extra:1")[0]));
+        assert!(!custom.is_complete(&parse("size:2kg")[0]));
+
+        let error = |schema: &str| Schema::from_str(schema).unwrap_err();
+        assert_eq!(
+            SchemaError {
+                line: 2,
+                message: "invalid constraint: range 1".to_string()
+            },
+            error("a required any\nb required range 1")
+        );
+        assert_eq!(2, error("a optional any\na required any").line);
+        assert_eq!(1, error("a sometimes any").line);
+        assert_eq!(
+            "line 1: unclosed {",
+            error("a required pattern x{2").to_string()
+        );
+    }
+
     #[test]
     fn run04() {
         let input = include_str!("./input.txt");
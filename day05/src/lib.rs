@@ -1,3 +1,11 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
 pub type Pass = Vec<bool>;
 
 pub fn parse(input: &str) -> Vec<Pass> {
@@ -16,7 +24,7 @@ fn binary(directions: &[bool], lower: u32, upper: u32) -> u32 {
     if directions.is_empty() {
         lower
     } else if directions[0] {
-        binary(&directions[1..], (lower + upper + 1) / 2, upper)
+        binary(&directions[1..], (lower + upper).div_ceil(2), upper)
     } else {
         binary(&directions[1..], lower, (lower + upper) / 2)
     }
@@ -51,6 +59,33 @@ pub fn part2(input: &[Pass]) -> u32 {
     unreachable!()
 }
 
+pub fn lint(input: &str) -> Vec<(usize, String)> {
+    let mut diagnostics = Vec::new();
+
+    for (i, row) in input.lines().enumerate() {
+        let length = row.chars().count();
+        if length != 10 {
+            diagnostics.push((i + 1, format!("expected 10 characters, found {}", length)));
+            continue;
+        }
+        for (pos, c) in row.chars().enumerate() {
+            let (allowed, section) = if pos < 7 {
+                (matches!(c, 'F' | 'B'), "F or B")
+            } else {
+                (matches!(c, 'L' | 'R'), "L or R")
+            };
+            if !allowed {
+                diagnostics.push((
+                    i + 1,
+                    format!("character {} is {:?}, expected {}", pos + 1, c, section),
+                ));
+            }
+        }
+    }
+
+    diagnostics
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,6 +103,19 @@ mod tests {
         assert_eq!(identify_seat(&parse_row("FBFBBFFRLR")), (44, 5))
     }
 
+    #[test]
+    fn test_lint() {
+        assert!(lint(include_str!("./input.txt")).is_empty());
+        assert_eq!(
+            vec![
+                (1, "expected 10 characters, found 9".to_string()),
+                (2, "character 3 is 'L', expected F or B".to_string()),
+                (2, "character 10 is 'B', expected L or R".to_string()),
+            ],
+            lint("FBFBBFFRL\nFBLBBFFRLB")
+        );
+    }
+
     #[test]
     fn run05() {
         let input = include_str!("./input.txt");
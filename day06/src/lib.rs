@@ -1,26 +1,31 @@
-use std::collections::HashSet;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
-pub fn parse(input: &str) -> Vec<Vec<HashSet<char>>> {
+extern crate alloc;
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+pub fn parse(input: &str) -> Vec<Vec<BTreeSet<char>>> {
     input
         .split("\n\n")
         .map(|group| group.lines().map(|line| line.chars().collect()).collect())
         .collect()
 }
 
-pub fn part1(input: &[Vec<HashSet<char>>]) -> usize {
+pub fn part1(input: &[Vec<BTreeSet<char>>]) -> usize {
     input
         .iter()
         .map(|group| {
             group
                 .iter()
                 .flat_map(|set| set.iter())
-                .collect::<HashSet<_>>()
+                .collect::<BTreeSet<_>>()
                 .len()
         })
         .sum()
 }
 
-pub fn part2(input: &[Vec<HashSet<char>>]) -> usize {
+pub fn part2(input: &[Vec<BTreeSet<char>>]) -> usize {
     input
         .iter()
         .map(|group| {
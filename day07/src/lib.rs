@@ -1,9 +1,14 @@
-#[macro_use]
-extern crate lazy_static;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
-use regex::Regex;
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::str::FromStr;
+extern crate alloc;
+
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use budget::{Budget, Exhausted};
+use core::fmt;
+use core::str::FromStr;
 
 #[derive(Debug)]
 pub struct Rule {
@@ -17,44 +22,38 @@ impl FromStr for Rule {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(
-                r"^(\w+ \w+ bags) contain (no other bags|(?:\d+ \w+ \w+ bags?(?:, )?)+)\.$"
-            )
-            .unwrap();
-        }
-
-        let captures = RE.captures(s).unwrap();
-        let bag = {
-            let mut parts = captures[1].split_ascii_whitespace();
-            (
-                parts.next().unwrap().to_string(),
-                parts.next().unwrap().to_string(),
-            )
-        };
-        let must_contain = {
-            let str = &captures[2];
-            if str == "no other bags" {
-                vec![]
-            } else {
-                str.split(", ")
-                    .map(|s| {
-                        let mut parts = s.split_ascii_whitespace();
-                        (
-                            parts.next().unwrap().parse().unwrap(),
-                            (
-                                parts.next().unwrap().to_string(),
-                                parts.next().unwrap().to_string(),
-                            ),
-                        )
-                    })
-                    .collect::<Vec<(u32, Bag)>>()
-            }
+        let (bag, contents) = s
+            .strip_suffix('.')
+            .and_then(|s| s.split_once(" bags contain "))
+            .ok_or(())?;
+        let bag = parse_bag(bag)?;
+        let must_contain = if contents == "no other bags" {
+            vec![]
+        } else {
+            contents
+                .split(", ")
+                .map(|s| {
+                    let (amount, bag) = s
+                        .strip_suffix(" bags")
+                        .or_else(|| s.strip_suffix(" bag"))
+                        .and_then(|s| s.split_once(' '))
+                        .ok_or(())?;
+                    Ok((amount.parse().map_err(|_| ())?, parse_bag(bag)?))
+                })
+                .collect::<Result<Vec<(u32, Bag)>, ()>>()?
         };
         Ok(Rule { bag, must_contain })
     }
 }
 
+fn parse_bag(s: &str) -> Result<Bag, ()> {
+    let mut parts = s.split_ascii_whitespace();
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(shade), Some(color), None) => Ok((shade.to_string(), color.to_string())),
+        _ => Err(()),
+    }
+}
+
 pub fn parse(input: &str) -> Vec<Rule> {
     input
         .lines()
@@ -64,7 +63,7 @@ pub fn parse(input: &str) -> Vec<Rule> {
 
 pub fn part1(input: &[Rule]) -> usize {
     let my_bag: Bag = ("shiny".to_string(), "gold".to_string());
-    let mut seen = HashSet::new();
+    let mut seen = BTreeSet::new();
     let mut todo = VecDeque::new();
 
     todo.push_back(&my_bag);
@@ -83,27 +82,116 @@ pub fn part1(input: &[Rule]) -> usize {
     seen.len() - 1
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CountError {
+    // the bag ends up inside itself, so the count is infinite
+    Cycle(Bag),
+    Exhausted(Exhausted),
+}
+
+impl fmt::Display for CountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CountError::Cycle((shade, color)) => {
+                write!(f, "{} {} bags contain themselves", shade, color)
+            }
+            CountError::Exhausted(exhausted) => exhausted.fmt(f),
+        }
+    }
+}
+
+impl From<Exhausted> for CountError {
+    fn from(exhausted: Exhausted) -> Self {
+        CountError::Exhausted(exhausted)
+    }
+}
+
 pub fn part2(input: &[Rule]) -> usize {
+    part2_with_budget(input, &mut Budget::unlimited()).unwrap()
+}
+
+// the budget bounds the number of distinct bags counted
+pub fn part2_with_budget(input: &[Rule], budget: &mut Budget) -> Result<usize, CountError> {
     let map = input
         .iter()
         .map(|Rule { bag, must_contain }| (bag, must_contain))
-        .collect::<HashMap<_, _>>();
+        .collect::<BTreeMap<_, _>>();
 
-    number_of_bags(&("shiny".to_string(), "gold".to_string()), &map) - 1
+    Ok(number_of_bags(&("shiny".to_string(), "gold".to_string()), &map, budget)? - 1)
 }
 
-fn number_of_bags(current: &Bag, rules: &HashMap<&Bag, &Vec<(u32, Bag)>>) -> usize {
-    rules[current]
-        .iter()
-        .map(|(amount, bag)| *amount as usize * number_of_bags(bag, rules))
-        .sum::<usize>()
-        + 1
+// Depth first with an explicit stack, so long chains of rules can't overflow the call stack.
+// A bag is counted once every bag it contains is, a bag that is reached again while it is still
+// waiting for its contents is part of a cycle.
+fn number_of_bags<'a>(
+    current: &'a Bag,
+    rules: &BTreeMap<&Bag, &'a Vec<(u32, Bag)>>,
+    budget: &mut Budget,
+) -> Result<usize, CountError> {
+    let mut counts = BTreeMap::new();
+    let mut waiting = BTreeSet::new();
+    let mut stack = vec![(current, false)];
+
+    while let Some((bag, contents_counted)) = stack.pop() {
+        if contents_counted {
+            let total = rules[bag]
+                .iter()
+                .map(|(amount, inner)| *amount as usize * counts[inner])
+                .sum::<usize>();
+            waiting.remove(bag);
+            counts.insert(bag, total + 1);
+        } else if !counts.contains_key(bag) {
+            budget.step()?;
+            if !waiting.insert(bag) {
+                return Err(CountError::Cycle(bag.clone()));
+            }
+            stack.push((bag, true));
+            stack.extend(rules[bag].iter().map(|(_, inner)| (inner, false)));
+        }
+    }
+    Ok(counts[current])
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_cyclic_rules() {
+        let input = "shiny gold bags contain 2 dark red bags.
+dark red bags contain 1 shiny gold bag.";
+        let parsed = parse(input);
+        let cycle = Err(CountError::Cycle(("shiny".to_string(), "gold".to_string())));
+        assert_eq!(cycle, part2_with_budget(&parsed, &mut Budget::unlimited()));
+        assert_eq!(
+            Err(CountError::Exhausted(Exhausted::Steps(1))),
+            part2_with_budget(&parsed, &mut Budget::steps(1))
+        );
+
+        // a bag contained in several others isn't a cycle, and is only counted once
+        let input = "shiny gold bags contain 2 dark red bags, 1 pale blue bag.
+pale blue bags contain 3 dark red bags.
+dark red bags contain no other bags.";
+        let parsed = parse(input);
+        assert_eq!(Ok(6), part2_with_budget(&parsed, &mut Budget::steps(3)));
+    }
+
+    #[test]
+    fn test_long_chain() {
+        // deeper than the call stack of a test thread would allow with one frame per bag
+        let input = (0..200_000)
+            .map(|i| format!("shade{} color bags contain 1 shade{} color bag.", i, i + 1))
+            .chain(Some(
+                "shade200000 color bags contain no other bags.".to_string(),
+            ))
+            .chain(Some(
+                "shiny gold bags contain 1 shade0 color bag.".to_string(),
+            ))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(200_001, part2(&parse(&input)));
+    }
+
     #[test]
     fn run07() {
         let input = include_str!("./input.txt");
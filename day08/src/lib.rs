@@ -1,6 +1,14 @@
-use std::str::FromStr;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
-#[derive(Debug)]
+extern crate alloc;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::thread;
+
+#[derive(Debug, Clone, Copy)]
 pub enum Instruction {
     Nop(i32),
     Acc(i32),
@@ -90,10 +98,52 @@ pub fn part2(mut input: Vec<Instruction>) -> i32 {
     unreachable!()
 }
 
+#[cfg(feature = "std")]
+pub fn part2_parallel(input: &[Instruction]) -> i32 {
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = input.len().div_ceil(threads).max(1);
+
+    thread::scope(|scope| {
+        let handles = (0..input.len())
+            .step_by(chunk_size)
+            .map(|start| {
+                scope.spawn(move || {
+                    let mut program = input.to_vec();
+                    (start..(start + chunk_size).min(input.len())).find_map(|i| {
+                        let original = program[i];
+                        program[i] = match original {
+                            Instruction::Nop(v) => Instruction::Jmp(v),
+                            Instruction::Jmp(v) => Instruction::Nop(v),
+                            Instruction::Acc(_) => return None,
+                        };
+                        let result = execute(&program).ok();
+                        program[i] = original;
+                        result
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // chunks are joined in order, so the lowest patched index wins like in part2
+        handles
+            .into_iter()
+            .find_map(|handle| handle.join().unwrap())
+            .unwrap()
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parallel() {
+        let input = include_str!("./input.txt");
+        let parsed = parse(input);
+        assert_eq!(part2_parallel(&parsed), part2(parsed));
+    }
+
     #[test]
     fn run08() {
         let input = include_str!("./input.txt");
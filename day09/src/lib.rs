@@ -1,3 +1,9 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
 pub fn parse(input: &str) -> Vec<u64> {
     input.lines().map(|l| l.parse().unwrap()).collect()
 }
@@ -27,8 +33,8 @@ fn is_valid_window(input: &[u64], target: u64) -> bool {
 pub fn part2(input: &[u64], part1_sol: u64) -> u64 {
     for i in 0..input.len() {
         let mut sum = 0u64;
-        let mut min = u64::max_value();
-        let mut max = u64::min_value();
+        let mut min = u64::MAX;
+        let mut max = u64::MIN;
         for (ii, value) in input[i..].iter().enumerate() {
             sum += value;
             min = min.min(*value);
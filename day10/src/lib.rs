@@ -1,3 +1,12 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
 pub fn parse(input: &str) -> Vec<u32> {
     input
         .lines()
@@ -13,7 +22,7 @@ pub fn part1(input: &[u32]) -> u32 {
     for jolt in input {
         let diff = (jolt - current) as usize;
         match diff {
-            3 | 2 | 1 | 0 => incr[diff] += 1,
+            0..=3 => incr[diff] += 1,
             _ => unreachable!(),
         }
         current = *jolt;
@@ -56,10 +65,58 @@ fn calculate_combinations(counters: &[u64], jolt: u32) -> u64 {
     total
 }
 
+pub fn lint(input: &str) -> Vec<(usize, String)> {
+    let mut diagnostics = Vec::new();
+    let mut adapters = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        match line.parse::<u32>() {
+            Ok(jolt) if jolt as usize + 3 >= 10_000 => diagnostics.push((
+                i + 1,
+                format!("joltage {} exceeds the supported 9996", jolt),
+            )),
+            Ok(jolt) => adapters.push((jolt, i + 1)),
+            Err(_) => diagnostics.push((i + 1, format!("{:?} is not a joltage", line))),
+        }
+    }
+
+    // part1 and part2 expect the adapters in sorted order, so check the gaps between neighbours
+    adapters.sort_unstable();
+
+    let mut previous = 0;
+    for (jolt, line) in adapters {
+        match jolt - previous {
+            0 => diagnostics.push((line, format!("duplicate adapter with joltage {}", jolt))),
+            1..=3 => {}
+            gap => diagnostics.push((
+                line,
+                format!("gap of {} jolts between {} and {}", gap, previous, jolt),
+            )),
+        }
+        previous = jolt;
+    }
+
+    diagnostics.sort();
+    diagnostics
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_lint() {
+        assert!(lint(include_str!("./input.txt")).is_empty());
+        assert_eq!(
+            vec![
+                (1, "gap of 4 jolts between 3 and 7".to_string()),
+                (2, "\"x\" is not a joltage".to_string()),
+                (4, "duplicate adapter with joltage 3".to_string()),
+            ],
+            lint("7\nx\n3\n3")
+        );
+    }
+
     #[test]
     fn run10() {
         let input = include_str!("./input.txt");
@@ -1,4 +1,11 @@
-use std::convert::TryFrom;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+use budget::{Budget, Exhausted};
+use core::convert::TryFrom;
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub enum SeatState {
@@ -32,9 +39,19 @@ pub fn parse(input: &str) -> Vec<Vec<SeatState>> {
 }
 
 pub fn part(input: &[Vec<SeatState>], seat_iter: SeatIter) -> usize {
+    part_with_budget(input, seat_iter, &mut Budget::unlimited()).unwrap()
+}
+
+// a custom SeatIter may never reach a fixed point, the budget bounds the number of rounds
+pub fn part_with_budget(
+    input: &[Vec<SeatState>],
+    seat_iter: SeatIter,
+    budget: &mut Budget,
+) -> Result<usize, Exhausted> {
     let mut old = input.to_owned();
 
     loop {
+        budget.step()?;
         let next = next_iter(&old, seat_iter);
         if next == old {
             break;
@@ -42,7 +59,7 @@ pub fn part(input: &[Vec<SeatState>], seat_iter: SeatIter) -> usize {
         old = next;
     }
 
-    count_taken(&old)
+    Ok(count_taken(&old))
 }
 
 pub type SeatIter = fn(input: &[Vec<SeatState>], x: usize, y: usize) -> SeatState;
@@ -225,6 +242,27 @@ pub fn next_state_part2(input: &[Vec<SeatState>], x: usize, y: usize) -> SeatSta
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_no_fixed_point() {
+        fn toggle(input: &[Vec<SeatState>], x: usize, y: usize) -> SeatState {
+            match input[y][x] {
+                SeatState::Empty => SeatState::Taken,
+                SeatState::Taken => SeatState::Empty,
+                s => s,
+            }
+        }
+
+        let parsed = parse("L.L\n#.#");
+        assert_eq!(
+            Err(Exhausted::Steps(50)),
+            part_with_budget(&parsed, toggle, &mut Budget::steps(50))
+        );
+        assert_eq!(
+            Ok(2),
+            part_with_budget(&parsed, next_state_part1, &mut Budget::steps(50))
+        );
+    }
+
     #[test]
     fn run11() {
         let input = include_str!("./input.txt");
@@ -1,4 +1,9 @@
-use std::str::FromStr;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::str::FromStr;
 
 #[derive(Debug)]
 pub enum Instruction {
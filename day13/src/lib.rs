@@ -1,4 +1,8 @@
-use modinverse::modinverse;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
 
 pub fn parse(input: &str) -> (i32, Vec<Option<i32>>) {
     let mut lines = input.lines();
@@ -23,12 +27,7 @@ pub fn part1(time: i32, busses: &[Option<i32>]) -> i32 {
     busses
         .iter()
         .flatten()
-        .map(|&bus_time| {
-            (
-                bus_time,
-                (time as f32 / bus_time as f32).ceil() as i32 * bus_time,
-            )
-        })
+        .map(|&bus_time| (bus_time, (time + bus_time - 1) / bus_time * bus_time))
         .min_by_key(|(_, time)| *time)
         .map(|(bus, closest)| bus * (closest - time))
         .unwrap()
@@ -40,10 +39,7 @@ pub fn part2(busses: &[Option<i32>]) -> i64 {
     let mapped_busses = busses
         .iter()
         .enumerate()
-        .flat_map(|(i, time)| match time {
-            None => None,
-            Some(t) => Some((i as i64, *t as i64)),
-        })
+        .flat_map(|(i, time)| time.map(|t| (i as i64, t as i64)))
         .collect::<Vec<(i64, i64)>>();
 
     let product = mapped_busses
@@ -62,6 +58,24 @@ pub fn part2(busses: &[Option<i32>]) -> i64 {
         % product
 }
 
+fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if a == 0 {
+        (b, 0, 1)
+    } else {
+        let (g, x, y) = egcd(b % a, a);
+        (g, y - (b / a) * x, x)
+    }
+}
+
+fn modinverse(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = egcd(a, m);
+    if g != 1 {
+        None
+    } else {
+        Some((x % m + m) % m)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,6 +94,13 @@ mod tests {
         assert_eq!(1202161486, part2(&busses));
     }
 
+    #[test]
+    fn test_modinverse() {
+        assert_eq!(Some(4), modinverse(3, 11));
+        assert_eq!(Some(1), modinverse(1, 7));
+        assert_eq!(None, modinverse(4, 8));
+    }
+
     #[test]
     fn run13() {
         let input = include_str!("./input.txt");
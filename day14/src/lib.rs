@@ -1,5 +1,14 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 use crate::Instruction::{SetMask, SetMemory};
-use std::collections::HashMap;
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
 
 #[derive(Debug, Clone)]
 pub enum Instruction {
@@ -77,7 +86,7 @@ pub fn apply_mask(val: u64, mask: &str) -> u64 {
 
 pub fn part2(input: &[Instruction]) -> u64 {
     let mut current_mask = String::new();
-    let mut memory = HashMap::<usize, u64>::new();
+    let mut memory = BTreeMap::<usize, u64>::new();
 
     for instruction in input.iter().cloned() {
         match instruction {
@@ -121,11 +130,58 @@ fn mask_addresses(val: usize, mask: &str) -> impl IntoIterator<Item = usize> {
             };
         }
 
-        addrs.extend(to_add.into_iter());
+        addrs.extend(to_add);
     }
     addrs
 }
 
+pub fn lint(input: &str) -> Vec<(usize, String)> {
+    let mut diagnostics = Vec::new();
+    let mut seen_mask = false;
+
+    for (i, line) in input.lines().enumerate() {
+        let mut split = line.split(" = ");
+        match (split.next(), split.next(), split.next()) {
+            (Some("mask"), Some(mask), None) => {
+                seen_mask = true;
+                let length = mask.chars().count();
+                if length != 36 {
+                    diagnostics.push((i + 1, format!("mask has {} bits, expected 36", length)));
+                }
+                if let Some(c) = mask.chars().find(|c| !matches!(c, 'X' | '0' | '1')) {
+                    diagnostics.push((i + 1, format!("mask contains invalid bit {:?}", c)));
+                }
+            }
+            (Some(left), Some(right), None) => {
+                let addr = left
+                    .strip_prefix("mem[")
+                    .and_then(|l| l.strip_suffix("]"))
+                    .and_then(|l| l.parse::<u64>().ok());
+                match addr {
+                    Some(addr) if addr >= 1 << 36 => {
+                        diagnostics.push((i + 1, format!("address {} exceeds 36 bits", addr)))
+                    }
+                    Some(_) => {}
+                    None => diagnostics.push((i + 1, format!("invalid address {:?}", left))),
+                }
+                match right.parse::<u64>() {
+                    Ok(val) if val >= 1 << 36 => {
+                        diagnostics.push((i + 1, format!("value {} exceeds 36 bits", val)))
+                    }
+                    Ok(_) => {}
+                    Err(_) => diagnostics.push((i + 1, format!("invalid value {:?}", right))),
+                }
+                if !seen_mask {
+                    diagnostics.push((i + 1, "memory write before the first mask".to_string()));
+                }
+            }
+            _ => diagnostics.push((i + 1, format!("unrecognized instruction {:?}", line))),
+        }
+    }
+
+    diagnostics
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,6 +193,21 @@ mod tests {
         assert_eq!(64, apply_mask(0, "XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X"));
     }
 
+    #[test]
+    fn test_lint() {
+        assert!(lint(include_str!("./input.txt")).is_empty());
+        assert_eq!(
+            vec![
+                (1, "memory write before the first mask".to_string()),
+                (2, "mask has 4 bits, expected 36".to_string()),
+                (2, "mask contains invalid bit 'Y'".to_string()),
+                (3, "value 68719476736 exceeds 36 bits".to_string()),
+                (4, "unrecognized instruction \"mem[8] 11\"".to_string()),
+            ],
+            lint("mem[8] = 11\nmask = X1Y0\nmem[8] = 68719476736\nmem[8] 11")
+        );
+    }
+
     #[test]
     fn run14() {
         let input = include_str!("./input.txt");
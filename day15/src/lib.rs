@@ -1,3 +1,10 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::vec;
+use alloc::vec::Vec;
+
 pub fn parse(input: &str) -> Vec<usize> {
     input.split(',').flat_map(|n| n.parse().ok()).collect()
 }
@@ -49,10 +56,10 @@ mod tests {
 
     #[test]
     fn test_samples() {
-        assert_eq!(436, part1(&vec![0, 3, 6]));
-        assert_eq!(1, part1(&vec![1, 3, 2]));
-        assert_eq!(10, part1(&vec![2, 1, 3]));
-        assert_eq!(27, part1(&vec![1, 2, 3]));
+        assert_eq!(436, part1(&[0, 3, 6]));
+        assert_eq!(1, part1(&[1, 3, 2]));
+        assert_eq!(10, part1(&[2, 1, 3]));
+        assert_eq!(27, part1(&[1, 2, 3]));
     }
 
     #[test]
@@ -1,5 +1,15 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use budget::{Budget, Exhausted};
+use core::ops::RangeInclusive;
 use regex::Regex;
-use std::ops::RangeInclusive;
 
 pub type Rule = (String, RangeInclusive<u32>, RangeInclusive<u32>);
 #[derive(Debug)]
@@ -71,17 +81,23 @@ pub fn part1(input: &Input) -> u32 {
         .sum()
 }
 
-#[allow(clippy::needless_range_loop)]
 pub fn part2(input: &Input) -> u64 {
+    part2_with_budget(input, &mut Budget::unlimited()).unwrap()
+}
+
+// if a field can't be uniquely assigned the loop makes no progress, the budget bounds its passes
+#[allow(clippy::needless_range_loop)]
+pub fn part2_with_budget(input: &Input, budget: &mut Budget) -> Result<u64, Exhausted> {
     let filtered = input
         .nearby_tickets
         .iter()
         .filter(|&ticket| ticket.0.iter().all(|&n| matches_a_rule(n, &input.rules)))
         .collect::<Vec<_>>();
     let mut current_rules = input.rules.iter().cloned().enumerate().collect::<Vec<_>>();
-    let mut assigned_rules = vec![usize::max_value(); input.rules.len()];
+    let mut assigned_rules = vec![usize::MAX; input.rules.len()];
 
     while !current_rules.is_empty() {
+        budget.step()?;
         'outer: for i in 0..input.my_ticket.0.len() {
             let mut found_rule = None;
             for (i_current_rules, (_, (_, first, second))) in current_rules.iter().enumerate() {
@@ -89,7 +105,7 @@ pub fn part2(input: &Input) -> u64 {
                     .iter()
                     .all(|&ticket| first.contains(&ticket.0[i]) || second.contains(&ticket.0[i]))
                 {
-                    if found_rule == None {
+                    if found_rule.is_none() {
                         found_rule = Some(i_current_rules);
                     } else {
                         continue 'outer; // skip this field, it is not uniquely assignable
@@ -103,12 +119,12 @@ pub fn part2(input: &Input) -> u64 {
         }
     }
 
-    assigned_rules
+    Ok(assigned_rules
         .iter()
         .enumerate()
         .filter(|(_, &rule_i)| input.rules[rule_i].0.starts_with("departure"))
         .map(|(ticket_i, _)| input.my_ticket.0[ticket_i] as u64)
-        .product()
+        .product())
 }
 
 fn matches_a_rule(i: u32, rules: &[Rule]) -> bool {
@@ -117,6 +133,95 @@ fn matches_a_rule(i: u32, rules: &[Rule]) -> bool {
         .any(|rule| rule.1.contains(&i) || rule.2.contains(&i))
 }
 
+pub fn lint(input: &str) -> Vec<(usize, String)> {
+    let headers = ["your ticket:", "nearby tickets:"];
+    let mut diagnostics = Vec::new();
+    let mut section = 0;
+    let mut expect_header = false;
+    let mut rule_count = 0;
+    let mut tickets_in_section = 0;
+
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            section += 1;
+            expect_header = true;
+            tickets_in_section = 0;
+            continue;
+        }
+        if section == 0 {
+            rule_count += 1;
+            if let Some(message) = lint_rule(line) {
+                diagnostics.push((i + 1, message));
+            }
+        } else if section > headers.len() {
+            diagnostics.push((
+                i + 1,
+                "unexpected line after the nearby tickets".to_string(),
+            ));
+        } else if expect_header {
+            expect_header = false;
+            if line != headers[section - 1] {
+                diagnostics.push((
+                    i + 1,
+                    format!("expected {:?}, found {:?}", headers[section - 1], line),
+                ));
+            }
+        } else {
+            tickets_in_section += 1;
+            if section == 1 && tickets_in_section > 1 {
+                diagnostics.push((i + 1, "more than one ticket of your own".to_string()));
+            }
+            if let Some(message) = lint_ticket(line, rule_count) {
+                diagnostics.push((i + 1, message));
+            }
+        }
+    }
+
+    if section < headers.len() {
+        diagnostics.push((
+            input.lines().count(),
+            format!("missing {:?} section", headers[section]),
+        ));
+    }
+
+    diagnostics
+}
+
+fn lint_rule(line: &str) -> Option<String> {
+    let (_, ranges) = match line.split_once(": ") {
+        Some(split) => split,
+        None => return Some(format!("rule {:?} has no field name", line)),
+    };
+    let ranges = ranges.split(" or ").collect::<Vec<_>>();
+    if ranges.len() != 2 {
+        return Some(format!("rule has {} ranges, expected 2", ranges.len()));
+    }
+    ranges.iter().find_map(|range| {
+        let bounds = range.split_once('-').and_then(|(lower, upper)| {
+            Some((lower.parse::<u32>().ok()?, upper.parse::<u32>().ok()?))
+        });
+        match bounds {
+            Some((lower, upper)) if lower <= upper => None,
+            _ => Some(format!("invalid range {:?}", range)),
+        }
+    })
+}
+
+fn lint_ticket(line: &str, rule_count: usize) -> Option<String> {
+    let fields = line.split(',').collect::<Vec<_>>();
+    if let Some(field) = fields.iter().find(|field| field.parse::<u32>().is_err()) {
+        Some(format!("ticket field {:?} is not a number", field))
+    } else if fields.len() != rule_count {
+        Some(format!(
+            "ticket has {} fields, but there are {} rules",
+            fields.len(),
+            rule_count
+        ))
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,6 +246,51 @@ nearby tickets:
         assert_eq!(71, part1(&parsed));
     }
 
+    #[test]
+    fn test_ambiguous_fields() {
+        let input = "departure a: 1-3 or 5-7
+departure b: 1-3 or 5-7
+
+your ticket:
+1,2
+
+nearby tickets:
+2,3";
+        let parsed = parse(input);
+        assert_eq!(
+            Err(Exhausted::Steps(100)),
+            part2_with_budget(&parsed, &mut Budget::steps(100))
+        );
+    }
+
+    #[test]
+    fn test_lint() {
+        assert!(lint(include_str!("./input.txt")).is_empty());
+
+        let input = "class: 1-3 or 5-7 or 9-11
+row: 6-11 or 44-33
+
+your ticket:
+7,1
+
+nearby tickets:
+7,3,47
+40,x";
+        assert_eq!(
+            vec![
+                (1, "rule has 3 ranges, expected 2".to_string()),
+                (2, "invalid range \"44-33\"".to_string()),
+                (8, "ticket has 3 fields, but there are 2 rules".to_string()),
+                (9, "ticket field \"x\" is not a number".to_string()),
+            ],
+            lint(input)
+        );
+        assert_eq!(
+            vec![(4, "missing \"nearby tickets:\" section".to_string())],
+            lint("class: 1-3 or 5-7\n\nyour ticket:\n7")
+        );
+    }
+
     #[test]
     fn run16() {
         let input = include_str!("./input.txt");
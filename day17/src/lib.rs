@@ -1,4 +1,11 @@
-use std::collections::HashSet;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::thread;
 
 pub fn parse(input: &str) -> Vec<Vec<bool>> {
     input
@@ -8,7 +15,16 @@ pub fn parse(input: &str) -> Vec<Vec<bool>> {
 }
 
 pub fn part1(input: &[Vec<bool>]) -> usize {
-    let mut current_set = HashSet::<(i32, i32, i32)>::new();
+    part1_with_threads(input, 1)
+}
+
+#[cfg(feature = "std")]
+pub fn part1_parallel(input: &[Vec<bool>]) -> usize {
+    part1_with_threads(input, available_threads())
+}
+
+fn part1_with_threads(input: &[Vec<bool>], threads: usize) -> usize {
+    let mut current_set = BTreeSet::<(i32, i32, i32)>::new();
 
     for (y, row) in input.iter().enumerate() {
         for (x, &is_set) in row.iter().enumerate() {
@@ -21,20 +37,53 @@ pub fn part1(input: &[Vec<bool>]) -> usize {
     for _ in 0..6 {
         //println!("filled: {}", current_set.len());
         //debug_print(&current_set);
-        current_set = calculate_iteration(&current_set);
+        current_set = calculate_iteration(&current_set, threads);
     }
     current_set.len()
 }
 
-fn calculate_iteration(last_state: &HashSet<(i32, i32, i32)>) -> HashSet<(i32, i32, i32)> {
-    let init = (
-        i32::max_value(),
-        i32::min_value(),
-        i32::max_value(),
-        i32::min_value(),
-        i32::max_value(),
-        i32::min_value(),
-    );
+#[cfg(feature = "std")]
+fn available_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+// splits the x range across threads, each thread evolves its own slab of cells
+#[cfg(feature = "std")]
+fn evolve_in_parallel<T, F>(xs: &[i32], threads: usize, evolve: F) -> BTreeSet<T>
+where
+    T: Send + Ord,
+    F: Fn(&[i32]) -> BTreeSet<T> + Copy + Send,
+{
+    if threads <= 1 {
+        return evolve(xs);
+    }
+
+    thread::scope(|scope| {
+        let handles = xs
+            .chunks(xs.len().div_ceil(threads))
+            .map(|chunk| scope.spawn(move || evolve(chunk)))
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+#[cfg(not(feature = "std"))]
+fn evolve_in_parallel<T, F>(xs: &[i32], _threads: usize, evolve: F) -> BTreeSet<T>
+where
+    F: Fn(&[i32]) -> BTreeSet<T>,
+{
+    evolve(xs)
+}
+
+fn calculate_iteration(
+    last_state: &BTreeSet<(i32, i32, i32)>,
+    threads: usize,
+) -> BTreeSet<(i32, i32, i32)> {
+    let init = (i32::MAX, i32::MIN, i32::MAX, i32::MIN, i32::MAX, i32::MIN);
     let (min_x, max_x, min_y, max_y, min_z, max_z) = last_state.iter().fold(
         init,
         |(min_x, max_x, min_y, max_y, min_z, max_z), (x, y, z)| {
@@ -49,28 +98,32 @@ fn calculate_iteration(last_state: &HashSet<(i32, i32, i32)>) -> HashSet<(i32, i
         },
     );
 
-    let mut next_set = HashSet::<(i32, i32, i32)>::new();
-
-    for x in (min_x - 1)..=(max_x + 1) {
-        for y in (min_y - 1)..=(max_y + 1) {
-            for z in (min_z - 1)..=(max_z + 1) {
-                match (
-                    last_state.contains(&(x, y, z)),
-                    count_neighbors(x, y, z, last_state),
-                ) {
-                    (true, 2) | (true, 3) | (false, 3) => {
-                        next_set.insert((x, y, z));
-                    }
-                    _ => {}
-                };
+    let xs = ((min_x - 1)..=(max_x + 1)).collect::<Vec<_>>();
+
+    evolve_in_parallel(&xs, threads, |xs: &[i32]| {
+        let mut next_set = BTreeSet::<(i32, i32, i32)>::new();
+
+        for &x in xs {
+            for y in (min_y - 1)..=(max_y + 1) {
+                for z in (min_z - 1)..=(max_z + 1) {
+                    match (
+                        last_state.contains(&(x, y, z)),
+                        count_neighbors(x, y, z, last_state),
+                    ) {
+                        (true, 2) | (true, 3) | (false, 3) => {
+                            next_set.insert((x, y, z));
+                        }
+                        _ => {}
+                    };
+                }
             }
         }
-    }
 
-    next_set
+        next_set
+    })
 }
 
-fn count_neighbors(x: i32, y: i32, z: i32, last_state: &HashSet<(i32, i32, i32)>) -> u32 {
+fn count_neighbors(x: i32, y: i32, z: i32, last_state: &BTreeSet<(i32, i32, i32)>) -> u32 {
     let mut counter = 0;
 
     for x_iter in (x - 1)..=(x + 1) {
@@ -89,7 +142,16 @@ fn count_neighbors(x: i32, y: i32, z: i32, last_state: &HashSet<(i32, i32, i32)>
 }
 
 pub fn part2(input: &[Vec<bool>]) -> usize {
-    let mut current_set = HashSet::<(i32, i32, i32, i32)>::new();
+    part2_with_threads(input, 1)
+}
+
+#[cfg(feature = "std")]
+pub fn part2_parallel(input: &[Vec<bool>]) -> usize {
+    part2_with_threads(input, available_threads())
+}
+
+fn part2_with_threads(input: &[Vec<bool>], threads: usize) -> usize {
+    let mut current_set = BTreeSet::<(i32, i32, i32, i32)>::new();
 
     for (y, row) in input.iter().enumerate() {
         for (x, &is_set) in row.iter().enumerate() {
@@ -102,23 +164,24 @@ pub fn part2(input: &[Vec<bool>]) -> usize {
     for _ in 0..6 {
         //println!("filled: {}", current_set.len());
         //debug_print(&current_set);
-        current_set = calculate_iteration_4d(&current_set);
+        current_set = calculate_iteration_4d(&current_set, threads);
     }
     current_set.len()
 }
 
 fn calculate_iteration_4d(
-    last_state: &HashSet<(i32, i32, i32, i32)>,
-) -> HashSet<(i32, i32, i32, i32)> {
+    last_state: &BTreeSet<(i32, i32, i32, i32)>,
+    threads: usize,
+) -> BTreeSet<(i32, i32, i32, i32)> {
     let init = (
-        i32::max_value(),
-        i32::min_value(),
-        i32::max_value(),
-        i32::min_value(),
-        i32::max_value(),
-        i32::min_value(),
-        i32::max_value(),
-        i32::min_value(),
+        i32::MAX,
+        i32::MIN,
+        i32::MAX,
+        i32::MIN,
+        i32::MAX,
+        i32::MIN,
+        i32::MAX,
+        i32::MIN,
     );
     let (min_x, max_x, min_y, max_y, min_z, max_z, min_w, max_w) = last_state.iter().fold(
         init,
@@ -136,27 +199,31 @@ fn calculate_iteration_4d(
         },
     );
 
-    let mut next_set = HashSet::<(i32, i32, i32, i32)>::new();
+    let xs = ((min_x - 1)..=(max_x + 1)).collect::<Vec<_>>();
 
-    for x in (min_x - 1)..=(max_x + 1) {
-        for y in (min_y - 1)..=(max_y + 1) {
-            for z in (min_z - 1)..=(max_z + 1) {
-                for w in (min_w - 1)..=(max_w + 1) {
-                    match (
-                        last_state.contains(&(x, y, z, w)),
-                        count_neighbors_4d(x, y, z, w, last_state),
-                    ) {
-                        (true, 2) | (true, 3) | (false, 3) => {
-                            next_set.insert((x, y, z, w));
-                        }
-                        _ => {}
-                    };
+    evolve_in_parallel(&xs, threads, |xs: &[i32]| {
+        let mut next_set = BTreeSet::<(i32, i32, i32, i32)>::new();
+
+        for &x in xs {
+            for y in (min_y - 1)..=(max_y + 1) {
+                for z in (min_z - 1)..=(max_z + 1) {
+                    for w in (min_w - 1)..=(max_w + 1) {
+                        match (
+                            last_state.contains(&(x, y, z, w)),
+                            count_neighbors_4d(x, y, z, w, last_state),
+                        ) {
+                            (true, 2) | (true, 3) | (false, 3) => {
+                                next_set.insert((x, y, z, w));
+                            }
+                            _ => {}
+                        };
+                    }
                 }
             }
         }
-    }
 
-    next_set
+        next_set
+    })
 }
 
 fn count_neighbors_4d(
@@ -164,7 +231,7 @@ fn count_neighbors_4d(
     y: i32,
     z: i32,
     w: i32,
-    last_state: &HashSet<(i32, i32, i32, i32)>,
+    last_state: &BTreeSet<(i32, i32, i32, i32)>,
 ) -> u32 {
     let mut counter = 0;
 
@@ -198,6 +265,17 @@ mod tests {
         assert_eq!(112, part1(&parsed));
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parallel() {
+        let input = ".#.
+..#
+###";
+        let parsed = parse(input);
+        assert_eq!(112, part1_parallel(&parsed));
+        assert_eq!(848, part2_parallel(&parsed));
+    }
+
     #[test]
     fn run17() {
         let input = include_str!("./input.txt");
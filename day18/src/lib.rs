@@ -0,0 +1,211 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::Peekable;
+use core::str::{Chars, FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Mul,
+}
+
+impl Operator {
+    fn apply(self, left: u64, right: u64) -> u64 {
+        match self {
+            Operator::Add => left + right,
+            Operator::Mul => left * right,
+        }
+    }
+}
+
+// operators bind tighter the higher their precedence, equal precedence evaluates left to right
+#[derive(Debug, Clone, Copy)]
+pub struct Precedence {
+    pub add: u8,
+    pub mul: u8,
+}
+
+pub const EQUAL: Precedence = Precedence { add: 0, mul: 0 };
+pub const ADDITION_FIRST: Precedence = Precedence { add: 1, mul: 0 };
+
+impl Precedence {
+    fn of(&self, operator: Operator) -> u8 {
+        match operator {
+            Operator::Add => self.add,
+            Operator::Mul => self.mul,
+        }
+    }
+}
+
+// operator chains are kept flat in source order, precedence is only applied when evaluating
+#[derive(Debug, PartialEq, Eq)]
+pub enum Expression {
+    Number(u64),
+    Chain(Box<Expression>, Vec<(Operator, Expression)>),
+}
+
+impl FromStr for Expression {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars().peekable();
+        let expression = parse_chain(&mut chars)?;
+        skip_whitespace(&mut chars);
+        match chars.next() {
+            None => Ok(expression),
+            Some(_) => Err(()),
+        }
+    }
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+        chars.next();
+    }
+}
+
+fn parse_chain(chars: &mut Peekable<Chars>) -> Result<Expression, ()> {
+    let first = parse_operand(chars)?;
+    let mut rest = Vec::new();
+
+    loop {
+        skip_whitespace(chars);
+        let operator = match chars.peek() {
+            Some('+') => Operator::Add,
+            Some('*') => Operator::Mul,
+            _ => break,
+        };
+        chars.next();
+        rest.push((operator, parse_operand(chars)?));
+    }
+
+    if rest.is_empty() {
+        Ok(first)
+    } else {
+        Ok(Expression::Chain(Box::new(first), rest))
+    }
+}
+
+fn parse_operand(chars: &mut Peekable<Chars>) -> Result<Expression, ()> {
+    skip_whitespace(chars);
+    match chars.next() {
+        Some('(') => {
+            let inner = parse_chain(chars)?;
+            skip_whitespace(chars);
+            match chars.next() {
+                Some(')') => Ok(inner),
+                _ => Err(()),
+            }
+        }
+        Some(c) if c.is_ascii_digit() => {
+            let mut number = c.to_digit(10).unwrap() as u64;
+            while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+                number = number * 10 + digit as u64;
+                chars.next();
+            }
+            Ok(Expression::Number(number))
+        }
+        _ => Err(()),
+    }
+}
+
+impl Expression {
+    pub fn evaluate(&self, precedence: &Precedence) -> u64 {
+        match self {
+            Expression::Number(n) => *n,
+            Expression::Chain(first, rest) => {
+                let mut values = vec![first.evaluate(precedence)];
+                let mut operators = Vec::<Operator>::new();
+
+                for (operator, operand) in rest {
+                    while let Some(&top) = operators.last() {
+                        if precedence.of(top) < precedence.of(*operator) {
+                            break;
+                        }
+                        reduce(&mut values, &mut operators);
+                    }
+                    operators.push(*operator);
+                    values.push(operand.evaluate(precedence));
+                }
+                while !operators.is_empty() {
+                    reduce(&mut values, &mut operators);
+                }
+
+                values[0]
+            }
+        }
+    }
+}
+
+fn reduce(values: &mut Vec<u64>, operators: &mut Vec<Operator>) {
+    let right = values.pop().unwrap();
+    let left = values.pop().unwrap();
+    values.push(operators.pop().unwrap().apply(left, right));
+}
+
+pub fn parse(input: &str) -> Vec<Expression> {
+    input
+        .lines()
+        .map(|line| Expression::from_str(line).unwrap())
+        .collect()
+}
+
+pub fn part1(input: &[Expression]) -> u64 {
+    input.iter().map(|e| e.evaluate(&EQUAL)).sum()
+}
+
+pub fn part2(input: &[Expression]) -> u64 {
+    input.iter().map(|e| e.evaluate(&ADDITION_FIRST)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: [(&str, u64, u64); 6] = [
+        ("1 + 2 * 3 + 4 * 5 + 6", 71, 231),
+        ("1 + (2 * 3) + (4 * (5 + 6))", 51, 51),
+        ("2 * 3 + (4 * 5)", 26, 46),
+        ("5 + (8 * 3 + 9 + 3 * 4 * 3)", 437, 1445),
+        ("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 12240, 669060),
+        (
+            "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
+            13632,
+            23340,
+        ),
+    ];
+
+    #[test]
+    fn test_samples() {
+        for &(input, part1_result, part2_result) in SAMPLES.iter() {
+            let parsed = parse(input);
+            assert_eq!(part1_result, part1(&parsed), "{}", input);
+            assert_eq!(part2_result, part2(&parsed), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        use Expression::*;
+        assert_eq!(
+            Ok(Chain(
+                Box::new(Number(2)),
+                vec![
+                    (Operator::Mul, Number(3)),
+                    (
+                        Operator::Add,
+                        Chain(Box::new(Number(4)), vec![(Operator::Mul, Number(5))])
+                    ),
+                ]
+            )),
+            Expression::from_str("2 * 3 + (4 * 5)")
+        );
+        assert_eq!(Err(()), Expression::from_str("2 * (3 + 4"));
+        assert_eq!(Err(()), Expression::from_str("2 3"));
+    }
+}
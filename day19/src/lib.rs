@@ -0,0 +1,162 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    Char(char),
+    Alternatives(Vec<Vec<usize>>),
+}
+
+impl FromStr for Rule {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(quoted) = s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            let mut chars = quoted.chars();
+            return match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(Rule::Char(c)),
+                _ => Err(()),
+            };
+        }
+
+        s.split(" | ")
+            .map(|sequence| {
+                sequence
+                    .split_ascii_whitespace()
+                    .map(|id| id.parse().map_err(|_| ()))
+                    .collect::<Result<Vec<usize>, ()>>()
+            })
+            .collect::<Result<_, _>>()
+            .map(Rule::Alternatives)
+    }
+}
+
+pub type Rules = BTreeMap<usize, Rule>;
+
+#[derive(Debug)]
+pub struct Input {
+    pub rules: Rules,
+    pub messages: Vec<String>,
+}
+
+pub fn parse(input: &str) -> Input {
+    let mut parts = input.split("\n\n");
+    let rules = parts
+        .next()
+        .unwrap()
+        .lines()
+        .map(|line| {
+            let (id, rule) = line.split_once(": ").unwrap();
+            (id.parse().unwrap(), rule.parse().unwrap())
+        })
+        .collect();
+    let messages = parts
+        .next()
+        .unwrap_or("")
+        .lines()
+        .map(|line| line.to_string())
+        .collect();
+
+    Input { rules, messages }
+}
+
+pub fn part1(input: &Input) -> usize {
+    count_matching(&input.rules, &input.messages)
+}
+
+pub fn part2(input: &Input) -> usize {
+    let mut rules = input.rules.clone();
+    rules.insert(8, Rule::Alternatives(vec![vec![42], vec![42, 8]]));
+    rules.insert(11, Rule::Alternatives(vec![vec![42, 31], vec![42, 11, 31]]));
+
+    count_matching(&rules, &input.messages)
+}
+
+fn count_matching(rules: &Rules, messages: &[String]) -> usize {
+    messages
+        .iter()
+        .filter(|message| matches(rules, message))
+        .count()
+}
+
+pub fn matches(rules: &Rules, message: &str) -> bool {
+    let message = message.chars().collect::<Vec<_>>();
+    match_rule(rules, 0, &message, 0).contains(&message.len())
+}
+
+// returns every position the message continues at after `rule` matched from `start`. Looping
+// rules terminate as long as they consume input before recursing, like rules 8 and 11 do.
+fn match_rule(rules: &Rules, rule: usize, message: &[char], start: usize) -> Vec<usize> {
+    match &rules[&rule] {
+        Rule::Char(c) if message.get(start) == Some(c) => vec![start + 1],
+        Rule::Char(_) => vec![],
+        Rule::Alternatives(alternatives) => {
+            let mut ends = alternatives
+                .iter()
+                .flat_map(|sequence| match_sequence(rules, sequence, message, start))
+                .collect::<Vec<_>>();
+            ends.sort_unstable();
+            ends.dedup();
+            ends
+        }
+    }
+}
+
+fn match_sequence(rules: &Rules, sequence: &[usize], message: &[char], start: usize) -> Vec<usize> {
+    let mut positions = vec![start];
+
+    for &rule in sequence {
+        positions = positions
+            .into_iter()
+            .flat_map(|position| match_rule(rules, rule, message, position))
+            .collect();
+        if positions.is_empty() {
+            break;
+        }
+    }
+
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample() {
+        let input = "0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: \"a\"
+5: \"b\"
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb";
+        let parsed = parse(input);
+        assert_eq!(Rule::Char('a'), parsed.rules[&4]);
+        assert_eq!(
+            Rule::Alternatives(vec![vec![2, 3], vec![3, 2]]),
+            parsed.rules[&1]
+        );
+        assert_eq!(2, part1(&parsed));
+    }
+
+    #[test]
+    fn test_looping_rules() {
+        let input = include_str!("./sample.txt");
+        let parsed = parse(input);
+        assert_eq!(3, part1(&parsed));
+        assert_eq!(12, part2(&parsed));
+    }
+}
@@ -0,0 +1,382 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    rows: Vec<Vec<bool>>,
+}
+
+impl FromStr for Image {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s
+            .lines()
+            .map(|line| line.chars().map(|c| c == '#').collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        if rows.iter().any(|row| row.len() != rows[0].len()) {
+            return Err(());
+        }
+        Ok(Image { rows })
+    }
+}
+
+impl fmt::Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows.iter().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for &pixel in row {
+                write!(f, "{}", if pixel { '#' } else { '.' })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// clockwise quarter turns, applied after the optional horizontal flip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Orientation {
+    pub rotations: u8,
+    pub flipped: bool,
+}
+
+impl Orientation {
+    pub fn all() -> impl Iterator<Item = Orientation> {
+        (0..8).map(|i| Orientation {
+            rotations: i % 4,
+            flipped: i >= 4,
+        })
+    }
+}
+
+impl Image {
+    pub fn new(rows: Vec<Vec<bool>>) -> Self {
+        Image { rows }
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows.first().map_or(0, |row| row.len())
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.rows[y][x]
+    }
+
+    pub fn count(&self) -> usize {
+        self.rows.iter().flatten().filter(|&&pixel| pixel).count()
+    }
+
+    pub fn rotated(&self) -> Image {
+        let height = self.height();
+        Image {
+            rows: (0..self.width())
+                .map(|y| (0..height).map(|x| self.rows[height - 1 - x][y]).collect())
+                .collect(),
+        }
+    }
+
+    pub fn flipped(&self) -> Image {
+        Image {
+            rows: self
+                .rows
+                .iter()
+                .map(|row| row.iter().rev().copied().collect())
+                .collect(),
+        }
+    }
+
+    pub fn oriented(&self, orientation: Orientation) -> Image {
+        let mut image = if orientation.flipped {
+            self.flipped()
+        } else {
+            self.clone()
+        };
+        for _ in 0..orientation.rotations {
+            image = image.rotated();
+        }
+        image
+    }
+
+    pub fn top(&self) -> Vec<bool> {
+        self.rows[0].clone()
+    }
+
+    pub fn bottom(&self) -> Vec<bool> {
+        self.rows[self.height() - 1].clone()
+    }
+
+    pub fn left(&self) -> Vec<bool> {
+        self.rows.iter().map(|row| row[0]).collect()
+    }
+
+    pub fn right(&self) -> Vec<bool> {
+        self.rows.iter().map(|row| row[row.len() - 1]).collect()
+    }
+
+    pub fn without_border(&self) -> Image {
+        Image {
+            rows: self.rows[1..self.height() - 1]
+                .iter()
+                .map(|row| row[1..row.len() - 1].to_vec())
+                .collect(),
+        }
+    }
+
+    // top left corners of every place where all set pixels of the pattern are set in the image
+    pub fn find(&self, pattern: &Image) -> Vec<(usize, usize)> {
+        if pattern.width() > self.width() || pattern.height() > self.height() {
+            return vec![];
+        }
+
+        let mut found = Vec::new();
+        for y in 0..=(self.height() - pattern.height()) {
+            for x in 0..=(self.width() - pattern.width()) {
+                let matches = pattern.rows.iter().enumerate().all(|(dy, row)| {
+                    row.iter()
+                        .enumerate()
+                        .all(|(dx, &pixel)| !pixel || self.rows[y + dy][x + dx])
+                });
+                if matches {
+                    found.push((x, y));
+                }
+            }
+        }
+        found
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tile {
+    pub id: u64,
+    pub image: Image,
+}
+
+impl FromStr for Tile {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (header, image) = s.split_once('\n').ok_or(())?;
+        let id = header
+            .strip_prefix("Tile ")
+            .and_then(|h| h.strip_suffix(':'))
+            .and_then(|h| h.parse().ok())
+            .ok_or(())?;
+        Ok(Tile {
+            id,
+            image: image.parse()?,
+        })
+    }
+}
+
+pub fn parse(input: &str) -> Vec<Tile> {
+    input
+        .split("\n\n")
+        .filter(|block| !block.trim().is_empty())
+        .map(|block| block.parse().unwrap())
+        .collect()
+}
+
+pub fn part1(input: &[Tile]) -> u64 {
+    corners(input).iter().map(|tile| tile.id).product()
+}
+
+pub fn part2(input: &[Tile]) -> usize {
+    let image = stitch(&assemble(input).unwrap());
+    water_roughness(&image)
+}
+
+// an edge and its reverse are the same edge once the tile is flipped
+fn canonical(edge: Vec<bool>) -> Vec<bool> {
+    let reversed = edge.iter().rev().copied().collect::<Vec<_>>();
+    edge.max(reversed)
+}
+
+fn edge_counts(tiles: &[Tile]) -> BTreeMap<Vec<bool>, usize> {
+    let mut counts = BTreeMap::new();
+    for tile in tiles {
+        for edge in [
+            tile.image.top(),
+            tile.image.bottom(),
+            tile.image.left(),
+            tile.image.right(),
+        ] {
+            *counts.entry(canonical(edge)).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+pub fn corners(tiles: &[Tile]) -> Vec<&Tile> {
+    let counts = edge_counts(tiles);
+    tiles
+        .iter()
+        .filter(|tile| {
+            let unmatched = [
+                tile.image.top(),
+                tile.image.bottom(),
+                tile.image.left(),
+                tile.image.right(),
+            ]
+            .iter()
+            .filter(|&edge| counts[&canonical(edge.clone())] == 1)
+            .count();
+            unmatched == 2
+        })
+        .collect()
+}
+
+// places every tile in a square grid, oriented so that neighbouring edges line up
+pub fn assemble(tiles: &[Tile]) -> Option<Vec<Vec<Tile>>> {
+    let side = (0..=tiles.len()).find(|side| side * side >= tiles.len())?;
+    if side * side != tiles.len() {
+        return None;
+    }
+
+    let counts = edge_counts(tiles);
+    let unmatched = |edge: Vec<bool>| counts[&canonical(edge)] == 1;
+
+    let corner = *corners(tiles).first()?;
+    let first = Orientation::all()
+        .map(|orientation| corner.image.oriented(orientation))
+        .find(|image| unmatched(image.top()) && unmatched(image.left()))?;
+
+    let mut used = tiles.iter().map(|tile| tile == corner).collect::<Vec<_>>();
+    let mut grid: Vec<Vec<Tile>> = Vec::with_capacity(side);
+
+    for y in 0..side {
+        let mut row: Vec<Tile> = Vec::with_capacity(side);
+        for x in 0..side {
+            if x == 0 && y == 0 {
+                row.push(Tile {
+                    id: corner.id,
+                    image: first.clone(),
+                });
+                continue;
+            }
+
+            let fits = |image: &Image| {
+                (x == 0 || image.left() == row[x - 1].image.right())
+                    && (y == 0 || image.top() == grid[y - 1][x].image.bottom())
+            };
+            let (i, image) = (0..tiles.len()).filter(|&i| !used[i]).find_map(|i| {
+                Orientation::all()
+                    .map(|orientation| tiles[i].image.oriented(orientation))
+                    .find(|image| fits(image))
+                    .map(|image| (i, image))
+            })?;
+
+            used[i] = true;
+            row.push(Tile {
+                id: tiles[i].id,
+                image,
+            });
+        }
+        grid.push(row);
+    }
+
+    Some(grid)
+}
+
+// joins the assembled tiles into one image, dropping the border of every tile
+pub fn stitch(grid: &[Vec<Tile>]) -> Image {
+    let mut rows = Vec::new();
+    for tile_row in grid {
+        let inner = tile_row
+            .iter()
+            .map(|tile| tile.image.without_border())
+            .collect::<Vec<_>>();
+        for y in 0..inner[0].height() {
+            rows.push(
+                inner
+                    .iter()
+                    .flat_map(|image| image.rows[y].iter().copied())
+                    .collect(),
+            );
+        }
+    }
+    Image { rows }
+}
+
+const SEA_MONSTER: &str = "..................#.
+#....##....##....###
+.#..#..#..#..#..#...";
+
+pub fn sea_monster() -> Image {
+    SEA_MONSTER.parse().unwrap()
+}
+
+pub fn water_roughness(image: &Image) -> usize {
+    let monster = sea_monster();
+
+    for orientation in Orientation::all() {
+        let oriented = image.oriented(orientation);
+        let found = oriented.find(&monster);
+        if found.is_empty() {
+            continue;
+        }
+
+        // monsters may overlap, so collect the covered pixels instead of multiplying
+        let mut covered = BTreeSet::new();
+        for (x, y) in found {
+            for (dy, row) in monster.rows.iter().enumerate() {
+                for (dx, &pixel) in row.iter().enumerate() {
+                    if pixel {
+                        covered.insert((x + dx, y + dy));
+                    }
+                }
+            }
+        }
+        return oriented.count() - covered.len();
+    }
+
+    image.count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orientations() {
+        let image = "#..\n##.".parse::<Image>().unwrap();
+        assert_eq!("##\n#.\n..", image.rotated().to_string());
+        assert_eq!("..#\n.##", image.flipped().to_string());
+        assert_eq!(image, image.rotated().rotated().rotated().rotated());
+
+        let all = Orientation::all()
+            .map(|o| image.oriented(o))
+            .collect::<Vec<_>>();
+        for (i, first) in all.iter().enumerate() {
+            assert!(all[i + 1..].iter().all(|second| first != second));
+        }
+    }
+
+    #[test]
+    fn test_sample() {
+        let input = include_str!("./sample.txt");
+        let parsed = parse(input);
+        assert_eq!(9, parsed.len());
+        assert_eq!(20899048083289, part1(&parsed));
+
+        let grid = assemble(&parsed).unwrap();
+        let image = stitch(&grid);
+        assert_eq!((24, 24), (image.width(), image.height()));
+        let mut corners = vec![grid[0][0].id, grid[0][2].id, grid[2][0].id, grid[2][2].id];
+        corners.sort_unstable();
+        assert_eq!(vec![1171, 1951, 2971, 3079], corners);
+        assert_eq!(273, part2(&parsed));
+    }
+}
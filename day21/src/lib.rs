@@ -0,0 +1,167 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+#[derive(Debug)]
+pub struct Food {
+    ingredients: BTreeSet<String>,
+    allergens: Vec<String>,
+}
+
+impl FromStr for Food {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (ingredients, allergens) = match s.split_once(" (contains ") {
+            Some((ingredients, allergens)) => (ingredients, allergens.strip_suffix(')').ok_or(())?),
+            None => (s, ""),
+        };
+        Ok(Food {
+            ingredients: ingredients
+                .split_ascii_whitespace()
+                .map(|i| i.to_string())
+                .collect(),
+            allergens: allergens
+                .split(", ")
+                .filter(|a| !a.is_empty())
+                .map(|a| a.to_string())
+                .collect(),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MappingError {
+    // every remaining allergen still has several candidate ingredients
+    Ambiguous(Vec<String>),
+    // no ingredient is left that could contain the allergen
+    Impossible(String),
+}
+
+impl fmt::Display for MappingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MappingError::Ambiguous(allergens) => {
+                write!(f, "ambiguous allergens: {}", allergens.join(", "))
+            }
+            MappingError::Impossible(allergen) => {
+                write!(f, "no ingredient can contain {}", allergen)
+            }
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Vec<Food> {
+    input
+        .lines()
+        .map(|line| Food::from_str(line).unwrap())
+        .collect()
+}
+
+// an allergen can only be in ingredients that appear in every food listing it
+fn candidates(foods: &[Food]) -> BTreeMap<&str, BTreeSet<&str>> {
+    let mut candidates = BTreeMap::<&str, BTreeSet<&str>>::new();
+
+    for food in foods {
+        let ingredients = food
+            .ingredients
+            .iter()
+            .map(String::as_str)
+            .collect::<BTreeSet<_>>();
+        for allergen in &food.allergens {
+            candidates
+                .entry(allergen)
+                .and_modify(|set| *set = &*set & &ingredients)
+                .or_insert_with(|| ingredients.clone());
+        }
+    }
+
+    candidates
+}
+
+pub fn part1(input: &[Food]) -> usize {
+    let suspicious = candidates(input)
+        .into_values()
+        .flatten()
+        .collect::<BTreeSet<_>>();
+
+    input
+        .iter()
+        .flat_map(|food| food.ingredients.iter())
+        .filter(|ingredient| !suspicious.contains(ingredient.as_str()))
+        .count()
+}
+
+pub fn resolve(input: &[Food]) -> Result<BTreeMap<String, String>, MappingError> {
+    let mut remaining = candidates(input);
+    let mut resolved = BTreeMap::new();
+
+    while !remaining.is_empty() {
+        if let Some((allergen, _)) = remaining.iter().find(|(_, set)| set.is_empty()) {
+            return Err(MappingError::Impossible(allergen.to_string()));
+        }
+
+        let (allergen, ingredient) = match remaining.iter().find(|(_, set)| set.len() == 1) {
+            Some((&allergen, set)) => (allergen, *set.iter().next().unwrap()),
+            None => {
+                return Err(MappingError::Ambiguous(
+                    remaining.keys().map(|a| a.to_string()).collect(),
+                ))
+            }
+        };
+
+        remaining.remove(allergen);
+        for set in remaining.values_mut() {
+            set.remove(ingredient);
+        }
+        resolved.insert(allergen.to_string(), ingredient.to_string());
+    }
+
+    Ok(resolved)
+}
+
+pub fn part2(input: &[Food]) -> Result<String, MappingError> {
+    // the map is ordered by allergen, which is the order the puzzle asks for
+    Ok(resolve(input)?.into_values().collect::<Vec<_>>().join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample() {
+        let input = "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)";
+        let parsed = parse(input);
+        assert_eq!(5, part1(&parsed));
+        assert_eq!(Ok("mxmxvkd,sqjhc,fvjkl".to_string()), part2(&parsed));
+    }
+
+    #[test]
+    fn test_unresolvable() {
+        let parsed = parse("a b (contains x, y)\nc");
+        assert_eq!(1, part1(&parsed));
+        assert_eq!(
+            Err(MappingError::Ambiguous(vec![
+                "x".to_string(),
+                "y".to_string()
+            ])),
+            part2(&parsed)
+        );
+
+        let parsed = parse("a (contains x, y)");
+        assert_eq!(
+            Err(MappingError::Impossible("y".to_string())),
+            part2(&parsed)
+        );
+    }
+}
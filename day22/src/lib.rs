@@ -0,0 +1,219 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::{BTreeSet, VecDeque};
+use alloc::vec::Vec;
+
+pub type Deck = VecDeque<u8>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Player {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Round {
+        cards: (u8, u8),
+        winner: Player,
+        sub_game: bool,
+    },
+    // the decks were in this state before, player one wins the game, in plain Combat too since
+    // it would loop forever otherwise
+    Repeated,
+}
+
+#[derive(Debug, Clone)]
+pub struct Game {
+    decks: (Deck, Deck),
+    recursive: bool,
+    seen: BTreeSet<Vec<u8>>,
+    rounds: usize,
+    winner: Option<Player>,
+}
+
+impl Game {
+    pub fn new(player1: Deck, player2: Deck, recursive: bool) -> Self {
+        Game {
+            decks: (player1, player2),
+            recursive,
+            seen: BTreeSet::new(),
+            rounds: 0,
+            winner: None,
+        }
+    }
+
+    pub fn decks(&self) -> (&Deck, &Deck) {
+        (&self.decks.0, &self.decks.1)
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    pub fn winner(&self) -> Option<Player> {
+        self.winner
+    }
+
+    // plays a single round, returns None once the game is over
+    pub fn step(&mut self) -> Option<Step> {
+        if self.winner.is_some() {
+            return None;
+        }
+
+        if !self.seen.insert(self.state()) {
+            self.winner = Some(Player::One);
+            return Some(Step::Repeated);
+        }
+
+        self.rounds += 1;
+        let first = self.decks.0.pop_front().unwrap();
+        let second = self.decks.1.pop_front().unwrap();
+
+        let sub_game = self.recursive
+            && self.decks.0.len() >= first as usize
+            && self.decks.1.len() >= second as usize;
+        let winner = if sub_game {
+            Game::new(
+                self.decks.0.iter().take(first as usize).copied().collect(),
+                self.decks.1.iter().take(second as usize).copied().collect(),
+                true,
+            )
+            .play()
+        } else if first > second {
+            Player::One
+        } else {
+            Player::Two
+        };
+
+        match winner {
+            Player::One => self.decks.0.extend([first, second]),
+            Player::Two => self.decks.1.extend([second, first]),
+        }
+
+        if self.decks.0.is_empty() {
+            self.winner = Some(Player::Two);
+        } else if self.decks.1.is_empty() {
+            self.winner = Some(Player::One);
+        }
+
+        Some(Step::Round {
+            cards: (first, second),
+            winner,
+            sub_game,
+        })
+    }
+
+    pub fn play(&mut self) -> Player {
+        while self.step().is_some() {}
+        self.winner.unwrap()
+    }
+
+    pub fn score(&self, player: Player) -> usize {
+        let deck = match player {
+            Player::One => &self.decks.0,
+            Player::Two => &self.decks.1,
+        };
+        deck.iter()
+            .rev()
+            .enumerate()
+            .map(|(i, &card)| (i + 1) * card as usize)
+            .sum()
+    }
+
+    // both decks with a separator, card values are never 0
+    fn state(&self) -> Vec<u8> {
+        let mut state = Vec::with_capacity(self.decks.0.len() + self.decks.1.len() + 1);
+        state.extend(self.decks.0.iter());
+        state.push(0);
+        state.extend(self.decks.1.iter());
+        state
+    }
+}
+
+pub fn parse(input: &str) -> (Deck, Deck) {
+    let mut decks = input.split("\n\n").map(|block| {
+        block
+            .lines()
+            .skip(1)
+            .map(|line| line.parse().unwrap())
+            .collect::<Deck>()
+    });
+    (decks.next().unwrap(), decks.next().unwrap())
+}
+
+pub fn part1(player1: &Deck, player2: &Deck) -> usize {
+    let mut game = Game::new(player1.clone(), player2.clone(), false);
+    let winner = game.play();
+    game.score(winner)
+}
+
+pub fn part2(player1: &Deck, player2: &Deck) -> usize {
+    let mut game = Game::new(player1.clone(), player2.clone(), true);
+    let winner = game.play();
+    game.score(winner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10";
+
+    #[test]
+    fn test_sample() {
+        let (player1, player2) = parse(SAMPLE);
+        assert_eq!(306, part1(&player1, &player2));
+        assert_eq!(291, part2(&player1, &player2));
+    }
+
+    #[test]
+    fn test_steps() {
+        let (player1, player2) = parse(SAMPLE);
+        let mut game = Game::new(player1, player2, false);
+        assert_eq!(
+            Some(Step::Round {
+                cards: (9, 5),
+                winner: Player::One,
+                sub_game: false
+            }),
+            game.step()
+        );
+        assert_eq!(
+            (
+                &[2, 6, 3, 1, 9, 5].iter().copied().collect::<Deck>(),
+                &[8, 4, 7, 10].iter().copied().collect::<Deck>()
+            ),
+            game.decks()
+        );
+        assert_eq!(Player::Two, game.play());
+        assert_eq!(29, game.rounds());
+        assert_eq!(None, game.step());
+    }
+
+    #[test]
+    fn test_infinite_game() {
+        let (player1, player2) = parse("Player 1:\n43\n19\n\nPlayer 2:\n2\n29\n14");
+        let mut game = Game::new(player1.clone(), player2.clone(), true);
+        assert_eq!(Player::One, game.play());
+
+        let mut game = Game::new(player1, player2, false);
+        assert_eq!(Player::One, game.play());
+        assert_eq!(6, game.rounds());
+        assert_eq!(None, game.step());
+    }
+}
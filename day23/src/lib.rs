@@ -0,0 +1,111 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+pub fn parse(input: &str) -> Vec<usize> {
+    input
+        .trim()
+        .chars()
+        .map(|c| c.to_digit(10).unwrap() as usize)
+        .collect()
+}
+
+pub fn part1(initial: &[usize]) -> String {
+    labels_after_one(&play(initial, initial.len(), 100))
+}
+
+pub fn part2(initial: &[usize]) -> u64 {
+    let next = play(initial, 1_000_000, 10_000_000);
+    let first = next[1];
+    first as u64 * next[first as usize] as u64
+}
+
+// next[label] is the label of the cup clockwise of `label`, index 0 is unused
+pub fn play(initial: &[usize], cups: usize, moves: usize) -> Vec<u32> {
+    // with three cups picked up there has to be a fourth one to put them next to
+    assert!(
+        cups >= 4 && cups >= initial.len(),
+        "can't play with {} cups starting from {} labels",
+        cups,
+        initial.len()
+    );
+
+    let mut next = vec![0u32; cups + 1];
+
+    let first = initial[0];
+    let mut last = first;
+    for label in initial[1..]
+        .iter()
+        .copied()
+        .chain((initial.len() + 1)..=cups)
+    {
+        next[last] = label as u32;
+        last = label;
+    }
+    next[last] = first as u32;
+
+    let mut current = first;
+    for _ in 0..moves {
+        let a = next[current] as usize;
+        let b = next[a] as usize;
+        let c = next[b] as usize;
+        next[current] = next[c];
+
+        let mut destination = current;
+        loop {
+            destination = if destination == 1 {
+                cups
+            } else {
+                destination - 1
+            };
+            if destination != a && destination != b && destination != c {
+                break;
+            }
+        }
+
+        next[c] = next[destination];
+        next[destination] = a as u32;
+        current = next[current] as usize;
+    }
+
+    next
+}
+
+pub fn labels_after_one(next: &[u32]) -> String {
+    let mut labels = String::new();
+    let mut cup = next[1] as usize;
+    while cup != 1 {
+        labels.push(core::char::from_digit(cup as u32, 10).unwrap());
+        cup = next[cup] as usize;
+    }
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample() {
+        let parsed = parse("389125467");
+        assert_eq!("92658374", labels_after_one(&play(&parsed, 9, 10)));
+        assert_eq!("67384529", part1(&parsed));
+        assert_eq!(149245887792, part2(&parsed));
+    }
+
+    #[test]
+    #[should_panic(expected = "can't play with 3 cups")]
+    fn test_too_few_cups() {
+        part1(&parse("123"));
+    }
+
+    #[test]
+    #[should_panic(expected = "can't play with 5 cups starting from 9 labels")]
+    fn test_fewer_cups_than_labels() {
+        play(&parse("389125467"), 5, 10);
+    }
+}
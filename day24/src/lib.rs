@@ -0,0 +1,164 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 6] = [
+        Direction::East,
+        Direction::SouthEast,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+        Direction::NorthEast,
+    ];
+
+    // axial coordinates (q, r), r grows towards the south
+    pub fn offset(self) -> (i32, i32) {
+        match self {
+            Direction::East => (1, 0),
+            Direction::SouthEast => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (0, -1),
+            Direction::NorthEast => (1, -1),
+        }
+    }
+}
+
+pub fn parse_line(line: &str) -> Option<Vec<Direction>> {
+    let mut directions = Vec::new();
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        let direction = match c {
+            'e' => Direction::East,
+            'w' => Direction::West,
+            's' | 'n' => match (c, chars.next()) {
+                ('s', Some('e')) => Direction::SouthEast,
+                ('s', Some('w')) => Direction::SouthWest,
+                ('n', Some('e')) => Direction::NorthEast,
+                ('n', Some('w')) => Direction::NorthWest,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        directions.push(direction);
+    }
+
+    Some(directions)
+}
+
+pub fn parse(input: &str) -> Vec<Vec<Direction>> {
+    input
+        .lines()
+        .map(|line| parse_line(line).unwrap())
+        .collect()
+}
+
+pub fn locate(directions: &[Direction]) -> (i32, i32) {
+    directions.iter().fold((0, 0), |(q, r), direction| {
+        let (dq, dr) = direction.offset();
+        (q + dq, r + dr)
+    })
+}
+
+pub fn flip_tiles(input: &[Vec<Direction>]) -> BTreeSet<(i32, i32)> {
+    let mut black = BTreeSet::new();
+
+    for directions in input {
+        let tile = locate(directions);
+        if !black.remove(&tile) {
+            black.insert(tile);
+        }
+    }
+
+    black
+}
+
+pub fn part1(input: &[Vec<Direction>]) -> usize {
+    flip_tiles(input).len()
+}
+
+pub fn part2(input: &[Vec<Direction>]) -> usize {
+    simulate(input, 100)
+}
+
+pub fn simulate(input: &[Vec<Direction>], days: usize) -> usize {
+    let mut current_set = flip_tiles(input);
+
+    for _ in 0..days {
+        current_set = calculate_iteration(&current_set);
+    }
+    current_set.len()
+}
+
+fn calculate_iteration(last_state: &BTreeSet<(i32, i32)>) -> BTreeSet<(i32, i32)> {
+    let init = (i32::MAX, i32::MIN, i32::MAX, i32::MIN);
+    let (min_q, max_q, min_r, max_r) =
+        last_state
+            .iter()
+            .fold(init, |(min_q, max_q, min_r, max_r), (q, r)| {
+                (min_q.min(*q), max_q.max(*q), min_r.min(*r), max_r.max(*r))
+            });
+
+    let mut next_set = BTreeSet::<(i32, i32)>::new();
+
+    for q in (min_q - 1)..=(max_q + 1) {
+        for r in (min_r - 1)..=(max_r + 1) {
+            match (
+                last_state.contains(&(q, r)),
+                count_neighbors(q, r, last_state),
+            ) {
+                (true, 1) | (true, 2) | (false, 2) => {
+                    next_set.insert((q, r));
+                }
+                _ => {}
+            };
+        }
+    }
+
+    next_set
+}
+
+fn count_neighbors(q: i32, r: i32, last_state: &BTreeSet<(i32, i32)>) -> usize {
+    Direction::ALL
+        .iter()
+        .map(|direction| direction.offset())
+        .filter(|(dq, dr)| last_state.contains(&(q + dq, r + dr)))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate() {
+        assert_eq!((0, 0), locate(&parse_line("nwwswee").unwrap()));
+        assert_eq!((0, 1), locate(&parse_line("esew").unwrap()));
+        assert_eq!(None, parse_line("sx"));
+    }
+
+    #[test]
+    fn test_sample() {
+        let input = include_str!("./sample.txt");
+        let parsed = parse(input);
+        assert_eq!(10, part1(&parsed));
+        assert_eq!(15, simulate(&parsed, 1));
+        assert_eq!(37, simulate(&parsed, 10));
+        assert_eq!(2208, part2(&parsed));
+    }
+}
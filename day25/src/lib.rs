@@ -0,0 +1,73 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+pub const MODULUS: u64 = 20201227;
+pub const SUBJECT: u64 = 7;
+
+pub fn parse(input: &str) -> (u64, u64) {
+    let mut lines = input.lines().map(|line| line.parse().unwrap());
+    (lines.next().unwrap(), lines.next().unwrap())
+}
+
+pub fn part1(card_key: u64, door_key: u64) -> u64 {
+    let loop_size = loop_size(card_key).unwrap();
+    transform(door_key, loop_size)
+}
+
+pub fn transform(subject: u64, loop_size: u64) -> u64 {
+    let mut result = 1;
+    let mut base = subject % MODULUS;
+    let mut exponent = loop_size;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % MODULUS;
+        }
+        base = base * base % MODULUS;
+        exponent >>= 1;
+    }
+
+    result
+}
+
+// baby-step giant-step: with m = ceil(sqrt(MODULUS)), the loop size is i * m + j where
+// public_key * SUBJECT^(-i * m) == SUBJECT^j
+pub fn loop_size(public_key: u64) -> Option<u64> {
+    let m = (1..).find(|m| m * m >= MODULUS).unwrap();
+
+    let mut baby_steps = BTreeMap::new();
+    let mut value = 1;
+    for j in 0..m {
+        baby_steps.entry(value).or_insert(j);
+        value = value * SUBJECT % MODULUS;
+    }
+
+    // SUBJECT^(MODULUS - 1) == 1, so this is SUBJECT^(-m)
+    let giant_step = transform(SUBJECT, MODULUS - 1 - m);
+    let mut gamma = public_key % MODULUS;
+    for i in 0..m {
+        if let Some(j) = baby_steps.get(&gamma) {
+            return Some(i * m + j);
+        }
+        gamma = gamma * giant_step % MODULUS;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample() {
+        assert_eq!(Some(8), loop_size(5764801));
+        assert_eq!(Some(11), loop_size(17807724));
+        assert_eq!(5764801, transform(SUBJECT, 8));
+        assert_eq!(14897079, part1(5764801, 17807724));
+        assert_eq!(14897079, part1(17807724, 5764801));
+    }
+}
@@ -0,0 +1,34 @@
+#![no_std]
+
+// Links every solver crate that supports `no_std` with its `std` feature disabled. Building this
+// crate for a target without a standard library fails as soon as one of them, or one of their
+// dependencies, needs std again:
+//
+//     cargo build -p nostd-check --target thumbv7em-none-eabihf
+
+pub use budget;
+pub use day01;
+pub use day02;
+pub use day03;
+pub use day04;
+pub use day05;
+pub use day06;
+pub use day07;
+pub use day08;
+pub use day09;
+pub use day10;
+pub use day11;
+pub use day12;
+pub use day13;
+pub use day14;
+pub use day15;
+pub use day16;
+pub use day17;
+pub use day18;
+pub use day19;
+pub use day20;
+pub use day21;
+pub use day22;
+pub use day23;
+pub use day24;
+pub use day25;